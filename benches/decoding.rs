@@ -0,0 +1,60 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use jobshop_python::jssp::{decode_reference, BlackBox, Decoder, Instance, NullaryOperator};
+use jobshop_python::jssp::can::Candidate;
+use jobshop_python::jssp::config::RunConfig;
+use jobshop_python::jssp::termination::Termination;
+use jobshop_python::jssp::rs::RandomSample;
+use jobshop_python::jssp::hc::HillClimber;
+use std::sync::Arc;
+
+const EVALUATIONS: usize = 5_000;
+const ACTIVE_EVALUATIONS: usize = 200;
+const ORDERS: usize = 1_000;
+
+fn decoding(c: &mut Criterion) {
+    let instance = Arc::new(Instance::generate("ta51", 50, 20, 1_828_772_259, 1_958_992_548));
+
+    let mut process = BlackBox::new(instance.clone(), String::from("bench"), RunConfig::new(Termination::evaluations(0)).with_seed(1));
+    let orders: Vec<Vec<usize>> = (0..ORDERS).map(|_| <BlackBox as NullaryOperator>::apply(&mut process).order).collect();
+
+    let mut group = c.benchmark_group("decode");
+    group.throughput(Throughput::Elements(ORDERS as u64));
+    group.bench_function("reference", |b| b.iter(|| orders.iter().map(|x| decode_reference(&instance, x).makespan).sum::<usize>()));
+    for &lazy in [false, true].iter() {
+        let config = RunConfig::new(Termination::evaluations(0)).with_seed(1).with_lazy_schedule(lazy);
+        let mut process = BlackBox::new(instance.clone(), String::from("bench"), config);
+        let schedule = if lazy { "lazy" } else { "eager" };
+
+        group.bench_function(schedule, |b|
+            b.iter(|| orders.iter().map(|x| Candidate::new(x.clone(), &mut process).makespan).sum::<usize>()));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("evaluations");
+    group.throughput(Throughput::Elements(EVALUATIONS as u64));
+    for &lazy in [false, true].iter() {
+        let config = RunConfig::new(Termination::evaluations(EVALUATIONS)).with_seed(1).with_lazy_schedule(lazy);
+        let schedule = if lazy { "lazy" } else { "eager" };
+
+        group.bench_with_input(BenchmarkId::new("random sample", schedule), &config, |b, config|
+            b.iter(|| RandomSample::new(instance.clone(), config.clone()).solve()));
+        group.bench_with_input(BenchmarkId::new("hill climber 1swap", schedule), &config, |b, config|
+            b.iter(|| HillClimber::new(instance.clone(), 1_676, "1swap", config.clone()).unwrap().solve()));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("active evaluations");
+    group.sample_size(10).throughput(Throughput::Elements(ACTIVE_EVALUATIONS as u64));
+    for &lazy in [false, true].iter() {
+        let config = RunConfig::new(Termination::evaluations(ACTIVE_EVALUATIONS)).with_seed(1)
+            .with_decoder(Decoder::Active).with_lazy_schedule(lazy);
+        let schedule = if lazy { "lazy" } else { "eager" };
+
+        group.bench_with_input(BenchmarkId::new("random sample", schedule), &config, |b, config|
+            b.iter(|| RandomSample::new(instance.clone(), config.clone()).solve()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, decoding);
+criterion_main!(benches);
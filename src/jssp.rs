@@ -1,9 +1,9 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
-use itertools::{Itertools, zip};
+use itertools::Itertools;
 use std::cmp::{max, min};
-use std::io::{BufReader, BufRead, Write};
+use std::io::{BufReader, BufRead, Read, Write};
 use std::path::Path;
 use std::fs::File;
 use std::rc::Rc;
@@ -11,6 +11,9 @@ use rand::prelude::{ThreadRng, StdRng};
 use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng, SeedableRng};
 use crate::jssp::can::Candidate;
+use crate::jssp::termination::Termination;
+use crate::jssp::config::RunConfig;
+use crate::jssp::incremental::PrefixCache;
 use std::sync::Arc;
 use chrono::{DateTime, Utc, Duration};
 use std::collections::hash_set::Union;
@@ -22,17 +25,47 @@ use serde::__private::Formatter;
 use custom_error::custom_error;
 
 pub mod can;
+pub mod graph;
+pub mod neighbourhood;
 pub mod rs;
 pub mod hc;
 pub mod ga;
 pub mod sa;
-
-#[derive(Clone)]
+pub mod ts;
+pub mod ils;
+pub mod vns;
+pub mod taillard;
+pub mod validator;
+pub mod termination;
+pub mod config;
+pub mod experiment;
+mod incremental;
+pub mod results;
+pub mod report;
+pub mod ecdf;
+pub mod gantt;
+pub mod convergence;
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum InstanceType {
     ORLIB,
     TAILLARD,
 }
 
+impl InstanceType {
+    pub fn detect(contents: &str) -> Option<Self> {
+        let mut lines = contents.lines().map(|x| x.trim()).filter(|x| !x.is_empty());
+        let header = lines.next()?;
+
+        if header.to_lowercase().starts_with("nb of jobs") {
+            let sections = lines.filter(|x| x.eq_ignore_ascii_case("times") || x.eq_ignore_ascii_case("machines")).count();
+            if sections == 2 { Some(InstanceType::TAILLARD) } else { None }
+        } else if header.split_whitespace().count() == 2 && header.split_whitespace().all(|x| x.parse::<usize>().is_ok()) {
+            Some(InstanceType::ORLIB)
+        } else { None }
+    }
+}
+
 impl Display for InstanceType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match *self {
@@ -42,63 +75,147 @@ impl Display for InstanceType {
     }
 }
 
-struct InstanceReader<'a> {
-    name: &'a str,
-    type_: &'a InstanceType,
+custom_error! {pub InstanceError
+    MissingFile{path: String} = "Failed to find the instance file {path}",
+    UnknownFormat{name: String} = "Failed to detect the format of the instance {name}",
+    Io{source: std::io::Error} = "Failed to read the instance",
+    MalformedHeader{line: usize} = "Malformed header at line {line}",
+    MissingSection{line: usize, section: String} = "Expected the {section} section at line {line}",
+    MalformedToken{line: usize, token: String} = "Malformed token '{token}' at line {line}",
+    WrongTokenCount{line: usize, expected: usize, found: usize} = "Expected {expected} tokens at line {line}, found {found}",
+    MachineOutOfRange{line: usize, machine: usize, machine_count: usize} = "Machine {machine} at line {line} is out of range for {machine_count} machines",
+    RepeatedMachine{line: usize, machine: usize} = "Machine {machine} is repeated at line {line}",
+    InconsistentJobLength{line: usize, expected: usize, found: usize} = "Expected {expected} operations at line {line}, found {found}",
+    MissingJobs{line: usize, expected: usize, found: usize} = "Expected {expected} jobs by line {line}, found {found}",
 }
 
-impl<'a> InstanceReader<'a> {
-    fn read(name: &'a str, type_: &'a InstanceType) -> Vec<Vec<usize>> {
-        Self { name, type_ }.read_instance_data()
+impl InstanceError {
+    fn from_io(path: &Path, error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => InstanceError::MissingFile { path: path.display().to_string() },
+            _ => InstanceError::Io { source: error },
+        }
     }
+}
 
-    fn read_instance_data(&self) -> Vec<Vec<usize>> {
-        match self.type_ {
-            InstanceType::ORLIB => self.read_orlib(),
-            InstanceType::TAILLARD => self.read_taillard(),
+struct InstanceReader<R: BufRead> {
+    lines: std::io::Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> InstanceReader<R> {
+    fn read(reader: R, type_: &InstanceType) -> Result<(Vec<usize>, Vec<Vec<usize>>), InstanceError> {
+        let mut reader = Self { lines: reader.lines(), line: 0 };
+        match type_ {
+            InstanceType::ORLIB => reader.read_orlib(),
+            InstanceType::TAILLARD => reader.read_taillard(),
         }
     }
 
-    fn read_orlib(&self) -> Vec<Vec<usize>> {
-        let s = format!("instances\\{}.txt", self.name);
-        let path = Path::new(s.as_str());
-
-        BufReader::new(File::open(path).unwrap()).lines().skip(1).map(|x| {
-            x.unwrap().split_whitespace()
-                .map(|x| x.parse().unwrap())
-                .collect()
-        }).collect()
-    }
-
-    fn read_taillard(&self) -> Vec<Vec<usize>> {
-        let s = format!("instances\\{}.txt", self.name);
-        let path = Path::new(s.as_str());
-
-        let contents = BufReader::new(File::open(path).unwrap());
-
-        let mut a: usize = 0;
-        let mut _b: usize = 0;
-        let mut times: Vec<Vec<usize>> = Vec::new();
-        let mut machines: Vec<Vec<usize>> = Vec::new();
-        for (i, line) in contents.lines().skip(1)
-            .map(|x| x.unwrap()).enumerate() {
-            if i == 0 {
-                let vec: Vec<usize> = line.split_whitespace()
-                    .map(|x| x.parse().unwrap()).collect_vec();
-                a = vec[0];
-                _b = vec[1];
-            } else if i == 1 || i == a + 2 { continue; } else if i < a + 2 {
-                times.push(line.split_whitespace().map(|x|
-                    x.parse::<usize>().unwrap()).collect())
-            } else {
-                machines.push(line.split_whitespace().map(|x|
-                    x.parse::<usize>().unwrap() - 1).collect())
+    fn read_orlib(&mut self) -> Result<(Vec<usize>, Vec<Vec<usize>>), InstanceError> {
+        let header = self.read_header(2)?;
+        let (n, m) = (header[0], header[1]);
+
+        let mut jobs: Vec<Vec<usize>> = Vec::with_capacity(n);
+        while jobs.len() < n {
+            let job = self.read_job(n, jobs.len(), m, 2)?;
+            self.check_machines(job.iter().step_by(2), m)?;
+            jobs.push(job);
+        }
+        Ok((header, jobs))
+    }
+
+    fn read_taillard(&mut self) -> Result<(Vec<usize>, Vec<Vec<usize>>), InstanceError> {
+        self.next_line()?.ok_or(InstanceError::MalformedHeader { line: self.line + 1 })?;
+        let header = self.read_header(6)?;
+        let (n, m) = (header[0], header[1]);
+
+        self.read_section("Times")?;
+        let mut times: Vec<Vec<usize>> = Vec::with_capacity(n);
+        while times.len() < n { times.push(self.read_job(n, times.len(), m, 1)?); }
+
+        self.read_section("Machines")?;
+        let mut machines: Vec<Vec<usize>> = Vec::with_capacity(n);
+        while machines.len() < n {
+            let job = self.read_job(n, machines.len(), m, 1)?;
+            if let Some(&machine) = job.iter().find(|&&x| x == 0) {
+                return Err(InstanceError::MachineOutOfRange { line: self.line, machine, machine_count: m });
             }
+            let job = job.into_iter().map(|x| x - 1).collect_vec();
+            self.check_machines(job.iter(), m)?;
+            machines.push(job);
+        }
+
+        Ok((header, times.into_iter().zip(machines).map(|(x, y)|
+            y.into_iter().interleave(x).collect()).collect()))
+    }
+
+    fn next_line(&mut self) -> Result<Option<String>, InstanceError> {
+        for line in &mut self.lines {
+            self.line += 1;
+            let line = line?;
+            if !line.trim().is_empty() { return Ok(Some(line)); }
+        }
+        Ok(None)
+    }
+
+    fn read_header(&mut self, expected: usize) -> Result<Vec<usize>, InstanceError> {
+        let line = self.next_line()?.ok_or(InstanceError::MalformedHeader { line: self.line + 1 })?;
+        match self.parse(&line) {
+            Ok(header) if header.len() == expected && header[0] > 0 && header[1] > 0 => Ok(header),
+            _ => Err(InstanceError::MalformedHeader { line: self.line }),
         }
+    }
 
-        zip(times, machines).map(|(x, y)|
-            y.into_iter().interleave(x).collect()).collect()
+    fn read_section(&mut self, section: &str) -> Result<(), InstanceError> {
+        match self.next_line()? {
+            Some(line) if line.trim().eq_ignore_ascii_case(section) => Ok(()),
+            _ => Err(InstanceError::MissingSection { line: self.line, section: String::from(section) }),
+        }
     }
+
+    fn read_job(&mut self, n: usize, found: usize, m: usize, width: usize) -> Result<Vec<usize>, InstanceError> {
+        let line = self.next_line()?.ok_or(InstanceError::MissingJobs { line: self.line, expected: n, found })?;
+        let job = self.parse(&line)?;
+        if job.len() % width != 0 {
+            return Err(InstanceError::WrongTokenCount { line: self.line, expected: m * width, found: job.len() });
+        }
+        if job.len() != m * width {
+            return Err(InstanceError::InconsistentJobLength { line: self.line, expected: m, found: job.len() / width });
+        }
+        Ok(job)
+    }
+
+    fn parse(&self, line: &str) -> Result<Vec<usize>, InstanceError> {
+        line.split_whitespace().map(|x| x.parse().map_err(|_|
+            InstanceError::MalformedToken { line: self.line, token: String::from(x) })).collect()
+    }
+
+    fn check_machines<'a>(&self, machines: impl Iterator<Item=&'a usize>, m: usize) -> Result<(), InstanceError> {
+        let mut visited = vec![false; m];
+        for &machine in machines {
+            if machine >= m {
+                return Err(InstanceError::MachineOutOfRange { line: self.line, machine, machine_count: m });
+            }
+            if visited[machine] { return Err(InstanceError::RepeatedMachine { line: self.line, machine }); }
+            visited[machine] = true;
+        }
+        Ok(())
+    }
+}
+
+const BEST_KNOWN: [(&str, usize); 7] = [
+    ("ft06", 55),
+    ("la01", 666),
+    ("abz5", 1234),
+    ("abz6", 943),
+    ("abz7", 656),
+    ("abz8", 648),
+    ("abz9", 678),
+];
+
+fn best_known(name: &str) -> Option<usize> {
+    BEST_KNOWN.iter().find(|(x, _)| x.eq_ignore_ascii_case(name)).map(|&(_, makespan)| makespan)
 }
 
 #[derive(Clone)]
@@ -108,36 +225,83 @@ pub struct Instance {
     type_: InstanceType,
     m: usize,
     n: usize,
-    termination_limit: usize,
-    is_timed: bool,
+    seeds: Option<(usize, usize)>,
+    best_known: Option<usize>,
+    known_lower_bound: Option<usize>,
 }
 
 impl Instance {
-    pub fn new(name: &str, type_: InstanceType, termination_limit: usize, is_timed: bool) -> Self {
-        let instance_data = InstanceReader::read(name, &type_);
-        Self {
+    pub fn new(name: &str, type_: InstanceType) -> Self {
+        let path = Path::new("instances").join(format!("{}.txt", name));
+        Self::from_path(path, type_).expect("Failed to load the instance")
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P, type_: InstanceType) -> Result<Self, InstanceError> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|error| InstanceError::from_io(path, error))?;
+
+        let name = path.file_stem().map(|x| x.to_string_lossy().to_string()).unwrap_or_default();
+        Self::from_reader(&name, BufReader::new(file), type_)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, InstanceError> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|error| InstanceError::from_io(path, error))?;
+
+        let name = path.file_stem().map(|x| x.to_string_lossy().to_string()).unwrap_or_default();
+        Self::load_reader(&name, BufReader::new(file))
+    }
+
+    pub fn load_reader<R: BufRead>(name: &str, mut reader: R) -> Result<Self, InstanceError> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+
+        let type_ = InstanceType::detect(&contents)
+            .ok_or(InstanceError::UnknownFormat { name: String::from(name) })?;
+        Self::from_reader(name, contents.as_bytes(), type_)
+    }
+
+    pub fn load_dir<P: AsRef<Path>>(path: P) -> Result<Vec<Self>, InstanceError> {
+        let path = path.as_ref();
+        let entries = std::fs::read_dir(path).map_err(|error| InstanceError::from_io(path, error))?;
+
+        let mut paths = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|x| x == "txt") { paths.push(path); }
+        }
+        paths.sort();
+
+        paths.into_iter().map(Self::load).collect()
+    }
+
+    pub fn from_reader<R: BufRead>(name: &str, reader: R, type_: InstanceType) -> Result<Self, InstanceError> {
+        let (header, instance_data) = InstanceReader::read(reader, &type_)?;
+        Ok(Self {
             name: String::from(name),
             type_,
-            termination_limit,
-            is_timed,
+            seeds: if header.len() > 3 { Some((header[2], header[3])) } else { None },
+            best_known: if header.len() > 5 { Some(header[4]) } else { best_known(name) },
+            known_lower_bound: if header.len() > 5 { Some(header[5]) } else { None },
 
             n: instance_data.len(),
             m: instance_data[0].len() / 2,
             jobs: instance_data,
-        }
+        })
     }
 }
 
 impl Serialize for Instance {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
         where S: Serializer {
-        let mut state = serializer.serialize_struct("instance", 7)?;
+        let mut state = serializer.serialize_struct("instance", 8)?;
         state.serialize_field("name", &self.name)?;
         state.serialize_field("type", &self.type_.to_string())?;
         state.serialize_field("machine_count", &self.m)?;
         state.serialize_field("job_count", &self.n)?;
-        state.serialize_field("termination_limit", &self.termination_limit)?;
-        state.serialize_field("is_timed", &self.is_timed)?;
+        state.serialize_field("seeds", &self.seeds)?;
+        state.serialize_field("best_known", &self.best_known)?;
+        state.serialize_field("known_lower_bound", &self.known_lower_bound)?;
         state.serialize_field("data", &self.jobs)?;
         state.end()
     }
@@ -148,47 +312,195 @@ pub struct CandidateSchedule { pub schedule: Vec<Vec<usize>> }
 
 impl CandidateSchedule { fn new(m: usize, n: usize) -> Self { Self { schedule: vec![vec![0; 3 * n]; m] } } }
 
-trait RepresentationMapping { fn map(&self, order: &Vec<usize>) -> CandidateSchedule; }
+pub struct SemiActive;
+
+pub struct Active;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decoder {
+    SemiActive,
+    Active,
+}
+
+custom_error! {pub DecoderError
+    Unknown{name: String} = "Unsupported decoder {name}, expected one of semi-active, active",
+}
+
+impl Display for Decoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Decoder::SemiActive => write!(f, "semi-active"),
+            Decoder::Active => write!(f, "active"),
+        }
+    }
+}
+
+impl std::str::FromStr for Decoder {
+    type Err = DecoderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "semi-active" => Ok(Decoder::SemiActive),
+            "active" => Ok(Decoder::Active),
+            _ => Err(DecoderError::Unknown { name: String::from(s) }),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct DecoderScratch {
+    machine_state: Vec<usize>,
+    machine_time: Vec<usize>,
+    job_state: Vec<usize>,
+    job_time: Vec<usize>,
+    priority: Vec<usize>,
+}
+
+impl DecoderScratch {
+    fn new(m: usize, n: usize) -> Self {
+        Self {
+            machine_state: vec![0; m],
+            machine_time: vec![0; m],
+            job_state: vec![0; n],
+            job_time: vec![0; n],
+            priority: vec![0; n * m],
+        }
+    }
+
+    fn reset(&mut self) {
+        self.machine_state.iter_mut().for_each(|x| *x = 0);
+        self.machine_time.iter_mut().for_each(|x| *x = 0);
+        self.job_state.iter_mut().for_each(|x| *x = 0);
+        self.job_time.iter_mut().for_each(|x| *x = 0);
+    }
+}
+
+/// The semi-active decoder as it was before the scratch buffers, allocating its state and the full schedule
+/// on every call. Kept as a reference for tests and benchmarks.
+pub fn decode_reference(instance: &Instance, order: &[usize]) -> Candidate {
+    let mut machine_state = vec![0; instance.m];
+    let mut machine_time = vec![0; instance.m];
+    let mut job_state = vec![0; instance.n];
+    let mut job_time = vec![0; instance.n];
+
+    let jobs = instance.jobs.clone();
+    let mut y = CandidateSchedule::new(instance.m, instance.n);
+
+    let (mut machine, mut job_step): (usize, usize);
+    let (mut start, mut end): (usize, usize);
+    for &job in order.iter() {
+        job_step = job_state[job] * 2;
+        machine = jobs[job][job_step];
+        job_state[job] += 1;
+
+        start = max(machine_time[machine], job_time[job]);
+        end = start + jobs[job][job_step + 1];
+
+        machine_time[machine] = end;
+        job_time[job] = end;
+
+        y.schedule[machine][machine_state[machine]] = job;
+        y.schedule[machine][machine_state[machine] + 1] = start;
+        y.schedule[machine][machine_state[machine] + 2] = end;
+        machine_state[machine] += 3;
+    }
+
+    let makespan = y.schedule.iter().map(|x| *x.last().unwrap()).max().expect("Failed to find makespan.");
+    Candidate { order: order.to_vec(), makespan, schedule: y.schedule }
+}
 
-impl RepresentationMapping for BlackBox {
-    fn map(&self, order: &Vec<usize>) -> CandidateSchedule {
-        let mut machine_state = vec![0; self.instance.m];
-        let mut machine_time = vec![0; self.instance.m];
-        let mut job_state = vec![0; self.instance.n];
-        let mut job_time = vec![0; self.instance.n];
+/// Decodes `order` into the scratch buffers and returns the makespan, writing the schedule only when given one.
+trait RepresentationMapping<T> { fn map(&mut self, order: &[usize], schedule: Option<&mut CandidateSchedule>) -> usize; }
 
-        let jobs = self.instance.jobs.clone();
-        let mut y = CandidateSchedule::new(self.instance.m, self.instance.n);
+impl RepresentationMapping<SemiActive> for BlackBox {
+    fn map(&mut self, order: &[usize], mut schedule: Option<&mut CandidateSchedule>) -> usize {
+        let jobs = &self.instance.jobs;
+        let x = &mut self.scratch;
+        x.reset();
 
         let (mut machine, mut job_step): (usize, usize);
         let (mut start, mut end): (usize, usize);
         for &job in order.iter() {
-            job_step = job_state[job] * 2;
+            job_step = x.job_state[job] * 2;
             machine = jobs[job][job_step];
-            job_state[job] += 1;
+            x.job_state[job] += 1;
 
-            start = max(machine_time[machine], job_time[job]);
+            start = max(x.machine_time[machine], x.job_time[job]);
             end = start + jobs[job][job_step + 1];
 
-            machine_time[machine] = end;
-            job_time[job] = end;
+            x.machine_time[machine] = end;
+            x.job_time[job] = end;
 
-            y.schedule[machine][machine_state[machine]] = job;
-            y.schedule[machine][machine_state[machine] + 1] = start;
-            y.schedule[machine][machine_state[machine] + 2] = end;
-            machine_state[machine] += 3;
+            if let Some(y) = schedule.as_deref_mut() {
+                y.schedule[machine][x.machine_state[machine]] = job;
+                y.schedule[machine][x.machine_state[machine] + 1] = start;
+                y.schedule[machine][x.machine_state[machine] + 2] = end;
+            }
+            x.machine_state[machine] += 3;
         }
-        y
+        *x.machine_time.iter().max().expect("Failed to find makespan.")
     }
 }
 
-trait SearchSpace { fn create(&self) -> Vec<usize>; }
+impl RepresentationMapping<Active> for BlackBox {
+    fn map(&mut self, order: &[usize], mut schedule: Option<&mut CandidateSchedule>) -> usize {
+        let (m, n) = (self.instance.m, self.instance.n);
+        let jobs = &self.instance.jobs;
+        let x = &mut self.scratch;
+        x.reset();
+
+        for (i, &job) in order.iter().enumerate() {
+            x.priority[job * m + x.job_state[job]] = i;
+            x.job_state[job] += 1;
+        }
+        x.job_state.iter_mut().for_each(|x| *x = 0);
 
-pub struct Counter;
+        let (mut machine, mut job_step, mut job): (usize, usize, usize);
+        let (mut start, mut end): (usize, usize);
+        for _ in 0..order.len() {
+            machine = 0;
+            end = usize::MAX;
+            for j in (0..n).filter(|&j| x.job_state[j] < m) {
+                job_step = x.job_state[j] * 2;
+                start = max(x.machine_time[jobs[j][job_step]], x.job_time[j]);
+                if start + jobs[j][job_step + 1] < end {
+                    end = start + jobs[j][job_step + 1];
+                    machine = jobs[j][job_step];
+                }
+            }
 
-pub struct Time;
+            job = (0..n)
+                .filter(|&j| x.job_state[j] < m && jobs[j][x.job_state[j] * 2] == machine)
+                .filter(|&j| max(x.machine_time[machine], x.job_time[j]) < end)
+                .min_by_key(|&j| x.priority[j * m + x.job_state[j]])
+                .expect("Failed to find the conflicting operation");
 
-pub trait TerminationCriterion<T> { fn should_terminate(&mut self) -> bool; }
+            job_step = x.job_state[job] * 2;
+            x.job_state[job] += 1;
+
+            start = max(x.machine_time[machine], x.job_time[job]);
+            end = start + jobs[job][job_step + 1];
+
+            x.machine_time[machine] = end;
+            x.job_time[job] = end;
+
+            if let Some(y) = schedule.as_deref_mut() {
+                y.schedule[machine][x.machine_state[machine]] = job;
+                y.schedule[machine][x.machine_state[machine] + 1] = start;
+                y.schedule[machine][x.machine_state[machine] + 2] = end;
+            }
+            x.machine_state[machine] += 3;
+        }
+        *x.machine_time.iter().max().expect("Failed to find makespan.")
+    }
+}
+
+fn derive_seeds(seed: u64, count: usize) -> Vec<u64> {
+    let mut random = StdRng::seed_from_u64(seed);
+    (0..count).map(|_| random.gen()).collect()
+}
+
+trait SearchSpace { fn create(&self) -> Vec<usize>; }
 
 pub trait NullaryOperator { fn apply(&mut self) -> Candidate; }
 
@@ -196,90 +508,238 @@ pub trait UnaryOperator1Swap { fn apply(&mut self, based: &Candidate) -> Candida
 
 pub trait UnaryOperatorNSwap { fn apply(&mut self, based: &Candidate) -> Candidate; }
 
+pub trait UnaryOperatorInsertion { fn apply(&mut self, based: &Candidate) -> Candidate; }
+
+pub trait UnaryOperatorInversion { fn apply(&mut self, based: &Candidate) -> Candidate; }
+
+pub trait UnaryOperatorScramble { fn apply(&mut self, based: &Candidate) -> Candidate; }
+
+pub type UnaryOperator = fn(&mut BlackBox, &Candidate) -> Candidate;
+
+const UNARY_OPERATORS: [(&str, UnaryOperator); 5] = [
+    ("1swap", <BlackBox as UnaryOperator1Swap>::apply),
+    ("nswap", <BlackBox as UnaryOperatorNSwap>::apply),
+    ("insertion", <BlackBox as UnaryOperatorInsertion>::apply),
+    ("inversion", <BlackBox as UnaryOperatorInversion>::apply),
+    ("scramble", <BlackBox as UnaryOperatorScramble>::apply),
+];
+
+custom_error! {pub OperatorError
+    Unknown{name: String, expected: String} = "Unsupported operator {name}, expected one of {expected}",
+}
+
+pub fn unary_operator(name: &str) -> Result<UnaryOperator, OperatorError> {
+    UNARY_OPERATORS.iter().find(|(x, _)| x.eq_ignore_ascii_case(name)).map(|&(_, operator)| operator)
+        .ok_or_else(|| OperatorError::Unknown { name: String::from(name), expected: unary_operator_names().join(", ") })
+}
+
+pub fn unary_operator_names() -> Vec<&'static str> {
+    UNARY_OPERATORS.iter().map(|&(name, _)| name).collect()
+}
+
 pub trait BinaryOperator { fn apply(&mut self, based_a: &Candidate, based_b: &Candidate) -> Candidate; }
 
+pub trait BinaryOperatorJox { fn apply(&mut self, based_a: &Candidate, based_b: &Candidate) -> Candidate; }
+
+pub trait BinaryOperatorGox { fn apply(&mut self, based_a: &Candidate, based_b: &Candidate) -> Candidate; }
+
+pub trait MultiParentOperator { fn apply(&mut self, parents: &[&Candidate]) -> Candidate; }
+
+/// Precedence preservative crossover: gene `i` of the child is the leftmost job of `parents[mask[i]]`
+/// not yet used, and each chosen gene is removed from every parent.
+pub fn ppx(parents: &[&[usize]], mask: &[usize]) -> Vec<usize> {
+    let length = parents[0].len();
+    assert!(mask.len() >= length, "Mask is shorter than the parents");
+    let jobs = parents[0].iter().max().map_or(0, |x| x + 1);
+
+    let occurrences = parents.iter().map(|parent| {
+        let mut seen = vec![0; jobs];
+        parent.iter().map(|&job| { seen[job] += 1; seen[job] - 1 }).collect_vec()
+    }).collect_vec();
+
+    let mut taken = vec![0; jobs];
+    let mut positions = vec![0; parents.len()];
+    let mut result = Vec::with_capacity(length);
+    for &choice in mask[..length].iter() {
+        let (parent, position) = (parents[choice], &mut positions[choice]);
+        while occurrences[choice][*position] < taken[parent[*position]] { *position += 1; }
+        taken[parent[*position]] += 1;
+        result.push(parent[*position]);
+    }
+    result
+}
+
+/// An improvement of a solver's current candidate, attributed to the neighbourhood that found it.
+#[derive(Clone, Serialize)]
+pub struct Improvement {
+    pub seconds: f64,
+    pub evaluations: usize,
+    pub from: usize,
+    pub to: usize,
+    pub neighbourhood: String,
+}
+
 #[derive(Clone)]
 pub struct BlackBox {
     start_time: DateTime<Utc>,
     end_time: DateTime<Utc>,
 
     metaheurestic: String,
-    instance: Instance,
+    instance: Arc<Instance>,
+    config: RunConfig,
     pub termination_counter: usize,
+    pub evaluations: usize,
     timer: std::time::Instant,
 
+    seed: u64,
     random: StdRng,
     best_candidate: Candidate,
     history: Vec<(f64, usize)>,
+    improvements: Vec<Improvement>,
 
     lower_bound: usize,
     upper_bound: usize,
-    should_terminate: fn(&mut Self) -> bool,
+    best_makespan: usize,
+    improved_at: (usize, f64),
+
+    mapping: fn(&mut Self, &[usize], Option<&mut CandidateSchedule>) -> usize,
+    scratch: DecoderScratch,
+    prefix: Option<PrefixCache>,
+}
+
+impl Display for BlackBox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Instance: {} ({} jobs, {} machines)", self.instance.name, self.instance.n, self.instance.m)?;
+        writeln!(f, "Metaheurestic: {} ({} decoder, seed {})", self.metaheurestic, self.config.decoder, self.seed)?;
+        write!(f, "Makespan: {} (lower bound {}", self.best_candidate.makespan, self.lower_bound)?;
+        if let (Some(best_known), Some(gap)) = (self.instance.best_known, self.gap()) {
+            write!(f, ", best known {}, gap {:.2}%", best_known, gap * 100.0)?;
+        }
+        writeln!(f, ")")?;
+        write!(f, "Evaluations: {} ({} iterations) in {:.2}s", self.evaluations, self.termination_counter, self.seconds())?;
+        if !self.improvements.is_empty() {
+            let counts = self.improvements.iter().map(|x| x.neighbourhood.as_str()).unique()
+                .map(|x| format!("{} {}", x, self.improvements.iter().filter(|y| y.neighbourhood == x).count()));
+            write!(f, "\nImprovements: {}", counts.format(", "))?;
+        }
+        Ok(())
+    }
 }
 
 impl Serialize for BlackBox {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error> where
         S: Serializer {
         let mut state = serializer.serialize_struct("candidate", 4)?;
-        state.serialize_field("author", &"Daniel Zdancewicz");
+        state.serialize_field("author", &"Daniel Zdancewicz")?;
 
         #[derive(Serialize)]
         struct InfoData<'a> {
             instance: &'a Instance,
             upper_bound: usize,
             lower_bound: usize,
+            gap: Option<f64>,
             metaheurestic: String,
+            decoder: String,
             start: String,
             end: String,
             timetaken: String,
+            seconds: f64,
+            label: &'a Option<String>,
             iteration_count: usize,
+            evaluations: usize,
+            termination: String,
+            seed: u64,
         }
         state.serialize_field("info", &InfoData {
             start: self.start_time.format("%Y-%m-%d %H:%M:%S").to_string(),
             end: self.end_time.format("%Y-%m-%d %H:%M:%S").to_string(),
             lower_bound: self.lower_bound,
             upper_bound: self.upper_bound,
+            gap: self.gap(),
             timetaken: (self.end_time - self.start_time).to_string(),
+            seconds: self.seconds(),
+            label: &self.config.label,
             metaheurestic: self.metaheurestic.clone(),
+            decoder: self.config.decoder.to_string(),
             instance: &self.instance,
             iteration_count: self.termination_counter,
-        });
-        state.serialize_field("solution", &self.best_candidate);
-        state.serialize_field("history", &self.history);
+            evaluations: self.evaluations,
+            termination: self.config.termination.to_string(),
+            seed: self.seed,
+        })?;
+        state.serialize_field("solution", &self.best_candidate)?;
+        state.serialize_field("history", &self.history)?;
+        if !self.improvements.is_empty() { state.serialize_field("improvements", &self.improvements)?; }
         state.end()
     }
 }
 
 impl BlackBox {
-    fn new(instance: Instance, metaheurestic: String) -> Self {
-        let should_terminate: fn(&mut Self) -> bool = match instance.is_timed {
-            true => <Self as TerminationCriterion<Time>>::should_terminate,
-            false => <Self as TerminationCriterion<Counter>>::should_terminate,
+    pub fn new(instance: Arc<Instance>, metaheurestic: String, config: RunConfig) -> Self {
+        let seed = config.seed.unwrap_or_else(|| StdRng::from_entropy().gen());
+        let mapping: fn(&mut Self, &[usize], Option<&mut CandidateSchedule>) -> usize = match config.decoder {
+            Decoder::SemiActive => <Self as RepresentationMapping<SemiActive>>::map,
+            Decoder::Active => <Self as RepresentationMapping<Active>>::map,
         };
+        let scratch = DecoderScratch::new(instance.m, instance.n);
+        let prefix = if config.decoder == Decoder::SemiActive { Some(PrefixCache::new(&instance)) } else { None };
 
         let mut bb = Self {
             instance,
-            should_terminate,
+            config,
+            best_makespan: usize::MAX,
+            improved_at: (0, 0.0),
             metaheurestic,
             start_time: Utc::now(),
             end_time: Utc::now(),
-            random: StdRng::from_entropy(),
+            seed,
+            random: StdRng::seed_from_u64(seed),
             best_candidate: Candidate { schedule: vec![], order: vec![], makespan: 0 },
             history: Vec::new(),
+            improvements: Vec::new(),
 
             lower_bound: 0,
             upper_bound: 0,
 
             termination_counter: 0,
+            evaluations: 0,
             timer: std::time::Instant::now(),
+
+            mapping,
+            scratch,
+            prefix,
         };
 
-        bb.best_candidate = <Self as NullaryOperator>::apply(&mut bb);
+        let mut candidate = <Self as NullaryOperator>::apply(&mut bb);
+        candidate.materialize(&mut bb);
+        bb.best_candidate = candidate;
         bb.lower_bound = bb.find_lower_bound();
         bb.upper_bound = bb.find_upper_bound();
+        bb.evaluations = 0;
         bb
     }
 
+    fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.random = StdRng::seed_from_u64(seed);
+    }
+
+    fn should_terminate(&mut self) -> bool {
+        self.termination_counter += 1;
+        self.config.termination.is_met(self)
+    }
+
+    /// Evaluates `order`, a copy of `based.order` changed from `first` onwards.
+    fn neighbour(&mut self, based: &Candidate, order: Vec<usize>, first: usize) -> Candidate {
+        match self.prefix.as_mut() {
+            Some(prefix) => {
+                self.evaluations += 1;
+                prefix.evaluate(&self.instance, based, order, first, self.config.lazy_schedule)
+            }
+            None => Candidate::new(order, self),
+        }
+    }
+
     pub(crate) fn finalize(mut self) -> Self {
         self.end_time = Utc::now();
         self
@@ -291,18 +751,34 @@ impl BlackBox {
     }
 
     fn update_candidate(&mut self, candidate: &Candidate) {
-        self.best_candidate = candidate.clone();
+        let mut candidate = candidate.clone();
+        candidate.materialize(self);
+        self.best_candidate = candidate;
+    }
+
+    fn record_improvement(&mut self, from: &Candidate, to: &Candidate, neighbourhood: String) {
+        self.improvements.push(Improvement {
+            seconds: self.timer.elapsed().as_secs_f64(),
+            evaluations: self.evaluations,
+            from: from.makespan,
+            to: to.makespan,
+            neighbourhood,
+        });
     }
 
     fn update_history(&mut self, candidate: &Candidate) {
         let current_time = self.timer.elapsed().as_secs_f64();
+        if candidate.makespan < self.best_makespan {
+            self.best_makespan = candidate.makespan;
+            self.improved_at = (self.evaluations, current_time);
+        }
 
         match self.history.last() {
             None => {
                 self.history.push((current_time, candidate.makespan));
             }
             Some(&(prev_time, makespan)) => {
-                if makespan > candidate.makespan || current_time - prev_time > 0.01 {
+                if makespan > candidate.makespan || current_time - prev_time > self.config.history_interval {
                     self.history.push((current_time, candidate.makespan));
                 }
             }
@@ -310,13 +786,31 @@ impl BlackBox {
     }
 
     pub fn save_to_file(&self) -> std::io::Result<()> {
+        match &self.config.output {
+            Some(path) => self.save_to(path),
+            None => {
+                let filepath = format!("solutions/{}.json", self.start_time.format("%d%m%Y-%H-%M-%S"));
+                self.save_to(Path::new(filepath.as_str()))
+            }
+        }
+    }
+
+    pub fn save_to<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
         let data = serde_json::to_string_pretty(&self)?;
-        let filepath = format!("solutions/{}.json", self.start_time.format("%d%m%Y-%H-%M-%S"));
-        let mut fp = File::create(Path::new(filepath.as_str()))?;
+        let mut fp = File::create(path)?;
         write!(fp, "{}", data)?;
         Ok(())
     }
 
+    pub fn seconds(&self) -> f64 {
+        (self.end_time - self.start_time).num_microseconds().unwrap_or(i64::MAX) as f64 / 1e6
+    }
+
+    pub fn gap(&self) -> Option<f64> {
+        self.instance.best_known.map(|x|
+            (self.best_candidate.makespan as f64 - x as f64) / x as f64)
+    }
+
     fn find_makespan(&self, y: &CandidateSchedule) -> usize {
         y.schedule.iter().map(|x| *x.last().unwrap())
             .max().expect("Failed to find makespan.")
@@ -360,21 +854,7 @@ impl BlackBox {
 
 impl SearchSpace for BlackBox {
     fn create(&self) -> Vec<usize> {
-        (0..self.instance.n).map(|i| vec![i; self.instance.m]).flatten().collect()
-    }
-}
-
-impl TerminationCriterion<Counter> for BlackBox {
-    fn should_terminate(&mut self) -> bool {
-        self.termination_counter += 1;
-        self.termination_counter >= self.instance.termination_limit
-    }
-}
-
-impl TerminationCriterion<Time> for BlackBox {
-    fn should_terminate(&mut self) -> bool {
-        self.termination_counter += 1;
-        self.timer.elapsed().as_secs() as usize >= self.instance.termination_limit
+        (0..self.instance.n).flat_map(|i| std::iter::repeat_n(i, self.instance.m)).collect()
     }
 }
 
@@ -382,7 +862,7 @@ impl NullaryOperator for BlackBox {
     fn apply(&mut self) -> Candidate {
         let mut vec = self.create();
         vec.shuffle(&mut self.random);
-        Candidate::new(&vec, self)
+        Candidate::new(vec, self)
     }
 }
 
@@ -394,7 +874,7 @@ impl UnaryOperator1Swap for BlackBox {
         let (i, mut j) = (self.random.gen_range(0..high), self.random.gen_range(0..high));
         while result[i] == result[j] { j = self.random.gen_range(0..high) }
         result.swap(i, j);
-        Candidate::new(&result, self)
+        self.neighbour(based, result, min(i, j))
     }
 }
 
@@ -402,56 +882,237 @@ impl UnaryOperatorNSwap for BlackBox {
     fn apply(&mut self, based: &Candidate) -> Candidate {
         let mut result = based.order.clone();
         let high = based.order.len();
+        let mut first = high;
 
         loop {
             let (i, mut j) = (self.random.gen_range(0..high), self.random.gen_range(0..high));
             while result[i] == result[j] { j = self.random.gen_range(0..high) }
             result.swap(i, j);
+            first = min(first, min(i, j));
 
             if self.random.gen() { break; }
         }
-        Candidate::new(&result, self)
+        self.neighbour(based, result, first)
+    }
+}
+
+impl UnaryOperatorInsertion for BlackBox {
+    fn apply(&mut self, based: &Candidate) -> Candidate {
+        let mut result = based.order.clone();
+
+        let high = based.order.len();
+        let (i, mut j) = (self.random.gen_range(0..high), self.random.gen_range(0..high));
+        while result[i] == result[j] { j = self.random.gen_range(0..high) }
+        let job = result.remove(i);
+        result.insert(j, job);
+        self.neighbour(based, result, min(i, j))
+    }
+}
+
+impl UnaryOperatorInversion for BlackBox {
+    fn apply(&mut self, based: &Candidate) -> Candidate {
+        let mut result = based.order.clone();
+
+        let high = based.order.len();
+        let (i, mut j) = (self.random.gen_range(0..high), self.random.gen_range(0..high));
+        while result[i] == result[j] { j = self.random.gen_range(0..high) }
+        let (i, j) = (min(i, j), max(i, j));
+        result[i..=j].reverse();
+        self.neighbour(based, result, i)
+    }
+}
+
+impl UnaryOperatorScramble for BlackBox {
+    fn apply(&mut self, based: &Candidate) -> Candidate {
+        let mut result = based.order.clone();
+
+        let high = based.order.len();
+        let (i, mut j) = (self.random.gen_range(0..high), self.random.gen_range(0..high));
+        while result[i] == result[j] { j = self.random.gen_range(0..high) }
+        let (i, j) = (min(i, j), max(i, j));
+        while result[i..=j] == based.order[i..=j] { result[i..=j].shuffle(&mut self.random); }
+        self.neighbour(based, result, i)
     }
 }
 
 impl BinaryOperator for BlackBox {
     fn apply(&mut self, based_a: &Candidate, based_b: &Candidate) -> Candidate {
-        let length: usize = based_a.order.len();
-        let mut visited_a: Vec<bool> = vec![false; length];
-        let mut visited_b: Vec<bool> = vec![false; length];
+        let mask = based_a.order.iter().map(|_| if self.random.gen::<bool>() { 0 } else { 1 }).collect_vec();
+        Candidate::new(ppx(&[&based_a.order, &based_b.order], &mask), self)
+    }
+}
 
-        let mut result: Vec<usize> = vec![0; length];
+impl MultiParentOperator for BlackBox {
+    fn apply(&mut self, parents: &[&Candidate]) -> Candidate {
+        let mask = parents[0].order.iter().map(|_| self.random.gen_range(0..parents.len())).collect_vec();
+        let orders = parents.iter().map(|x| x.order.as_slice()).collect_vec();
+        Candidate::new(ppx(&orders, &mask), self)
+    }
+}
 
-        let mut result_i: usize = 0;
-        let mut a_i: usize = 0;
-        let mut b_i: usize = 0;
+impl BinaryOperatorJox for BlackBox {
+    fn apply(&mut self, based_a: &Candidate, based_b: &Candidate) -> Candidate {
+        let kept = (0..self.instance.n).map(|_| self.random.gen::<bool>()).collect_vec();
 
-        loop {
-            let add = if self.random.gen::<bool>() { based_a.order[a_i] } else { based_b.order[b_i] };
-            result[result_i] = add;
-            result_i += 1;
-
-            if result_i >= length { return Candidate::new(&result, self); }
-
-            let mut i = a_i;
-            loop {
-                if based_a.order[i] == add && !visited_a[i] {
-                    visited_a[i] = true;
-                    break;
-                };
-                i += 1
-            }
-            while visited_a[a_i] { a_i += 1 }
-
-            let mut i = b_i;
-            loop {
-                if based_b.order[i] == add && !visited_b[i] {
-                    visited_b[i] = true;
-                    break;
-                };
-                i += 1
-            }
-            while visited_b[b_i] { b_i += 1 }
+        let mut rest = based_b.order.iter().filter(|&&job| !kept[job]);
+        let result = based_a.order.iter()
+            .map(|&job| if kept[job] { job } else { *rest.next().expect("Parents differ in job counts") })
+            .collect();
+        Candidate::new(result, self)
+    }
+}
+
+impl BinaryOperatorGox for BlackBox {
+    fn apply(&mut self, based_a: &Candidate, based_b: &Candidate) -> Candidate {
+        let length = based_a.order.len();
+        let size = self.random.gen_range(max(length / 3, 1)..=max(length / 2, 1));
+        let start = self.random.gen_range(0..=length - size);
+        let donor = &based_b.order[start..start + size];
+
+        let (mut low, mut high) = (vec![0; self.instance.n], vec![0; self.instance.n]);
+        for &job in based_b.order[..start].iter() { low[job] += 1; }
+        high.clone_from(&low);
+        for &job in donor.iter() { high[job] += 1; }
+
+        let mut seen = vec![0; self.instance.n];
+        let mut result = Vec::with_capacity(length);
+        for &job in based_a.order.iter() {
+            let occurrence = seen[job];
+            seen[job] += 1;
+            if job == donor[0] && occurrence == low[job] { result.extend_from_slice(donor); }
+            if occurrence < low[job] || occurrence >= high[job] { result.push(job); }
+        }
+        Candidate::new(result, self)
+    }
+}
+
+/// The 15 x 10 Taillard instance the tests decode and search on.
+#[cfg(test)]
+pub(crate) fn test_instance() -> Arc<Instance> {
+    Arc::new(Instance::generate("tai", 15, 10, 840_612_802, 398_197_754))
+}
+
+/// A seeded process on `test_instance` for calling operators directly; it never terminates on its own.
+#[cfg(test)]
+pub(crate) fn test_process(seed: u64) -> BlackBox {
+    BlackBox::new(test_instance(), String::from("test"), RunConfig::new(Termination::evaluations(0)).with_seed(seed))
+}
+
+/// The parts of a finished run that a seed fixes; history times are left out as they depend on the clock.
+#[cfg(test)]
+pub(crate) fn outcome(process: &BlackBox) -> (Vec<usize>, usize, usize, usize, Vec<usize>) {
+    (process.best_candidate.order.clone(), process.best_candidate.makespan, process.evaluations,
+     process.termination_counter, process.history.iter().map(|x| x.1).dedup().collect())
+}
+
+/// Panics unless every tag in `markup` is closed in order; declarations such as `<!DOCTYPE>` are skipped.
+#[cfg(test)]
+pub(crate) fn assert_well_formed(markup: &str) {
+    let mut open: Vec<&str> = Vec::new();
+    for tag in markup.split('<').skip(1).map(|x| &x[..x.find('>').expect("Unterminated tag")]) {
+        if tag.starts_with('!') || tag.ends_with('/') { continue; }
+        match tag.strip_prefix('/') {
+            Some(name) => assert_eq!(open.pop(), Some(name), "Mismatched closing tag {}", name),
+            None => open.push(tag.split_whitespace().next().unwrap()),
         }
     }
+    assert!(open.is_empty(), "Unclosed tags {:?}", open);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+
+    /// First and last positions where `next` differs from `based`.
+    fn changed(based: &[usize], next: &[usize]) -> (usize, usize) {
+        let first = (0..based.len()).find(|&x| based[x] != next[x]).expect("Failed to change the order");
+        let last = (0..based.len()).rev().find(|&x| based[x] != next[x]).unwrap();
+        (first, last)
+    }
+
+    #[test]
+    fn insertion_inversion_and_scramble_rearrange_one_range() {
+        let mut process = test_process(12);
+        let based = <BlackBox as NullaryOperator>::apply(&mut process);
+        let jobs = based.order.iter().copied().sorted().collect_vec();
+
+        for _ in 0..200 {
+            let next = <BlackBox as UnaryOperatorInsertion>::apply(&mut process, &based);
+            let (first, last) = changed(&based.order, &next.order);
+            let (mut left, mut right) = (based.order[first..=last].to_vec(), based.order[first..=last].to_vec());
+            left.rotate_left(1);
+            right.rotate_right(1);
+            assert!(next.order[first..=last] == left[..] || next.order[first..=last] == right[..]);
+
+            let next = <BlackBox as UnaryOperatorInversion>::apply(&mut process, &based);
+            let (first, last) = changed(&based.order, &next.order);
+            assert!(next.order[first..=last].iter().eq(based.order[first..=last].iter().rev()));
+
+            let next = <BlackBox as UnaryOperatorScramble>::apply(&mut process, &based);
+            changed(&based.order, &next.order);
+            assert_eq!(next.order.iter().copied().sorted().collect_vec(), jobs);
+        }
+    }
+
+    #[test]
+    fn registry_finds_operators_by_name() {
+        assert_eq!(unary_operator_names(), vec!["1swap", "nswap", "insertion", "inversion", "scramble"]);
+        assert!(unary_operator("NSwap").is_ok());
+        let error = unary_operator("bogus").err().unwrap();
+        assert_eq!(error.to_string(), "Unsupported operator bogus, expected one of 1swap, nswap, insertion, inversion, scramble");
+    }
+
+    const ORLIB: &str = "2 3\n0 3 1 2 2 2\n2 4 0 1 1 3\n";
+    const TAILLARD: &str = "Nb of jobs, Nb of Machines, Time seed, Machine seed, Upper bound, Lower bound\n\
+        2 3 1 2 11 9\nTimes\n3 2 2\n4 1 3\nMachines\n1 2 3\n3 1 2\n";
+
+    fn load(contents: &str) -> Result<Instance, InstanceError> {
+        Instance::load_reader("test", contents.as_bytes())
+    }
+
+    #[test]
+    fn detects_instance_formats() {
+        assert_eq!(InstanceType::detect(ORLIB), Some(InstanceType::ORLIB));
+        assert_eq!(InstanceType::detect(TAILLARD), Some(InstanceType::TAILLARD));
+        assert_eq!(InstanceType::detect("Nb of jobs\n2 3\nTimes\n"), None);
+        assert_eq!(InstanceType::detect("two three\n"), None);
+        assert_eq!(InstanceType::detect(""), None);
+    }
+
+    #[test]
+    fn reads_both_formats_into_the_same_jobs() {
+        let orlib = load(ORLIB).unwrap();
+        let taillard = load(TAILLARD).unwrap();
+        assert_eq!(orlib.jobs, taillard.jobs);
+        assert_eq!((orlib.n, orlib.m), (2, 3));
+        assert_eq!((orlib.type_, taillard.type_), (InstanceType::ORLIB, InstanceType::TAILLARD));
+        assert_eq!((orlib.seeds, orlib.best_known, orlib.known_lower_bound), (None, None, None));
+        assert_eq!((taillard.seeds, taillard.best_known, taillard.known_lower_bound), (Some((1, 2)), Some(11), Some(9)));
+    }
+
+    #[test]
+    fn reports_malformed_instances() {
+        assert!(matches!(load("what is this\n"), Err(InstanceError::UnknownFormat { .. })));
+        assert!(matches!(Instance::from_reader("test", "2\n".as_bytes(), InstanceType::ORLIB),
+            Err(InstanceError::MalformedHeader { line: 1 })));
+        assert!(matches!(load("2 3\n0 3 1 2 2\n"), Err(InstanceError::WrongTokenCount { line: 2, expected: 6, found: 5 })));
+        assert!(matches!(load("2 3\n0 3 1 2\n"), Err(InstanceError::InconsistentJobLength { line: 2, expected: 3, found: 2 })));
+        assert!(matches!(load("2 3\n0 3 1 x 2 2\n"), Err(InstanceError::MalformedToken { line: 2, .. })));
+        assert!(matches!(load("2 3\n0 3 1 2 3 2\n"), Err(InstanceError::MachineOutOfRange { line: 2, machine: 3, machine_count: 3 })));
+        assert!(matches!(load("2 3\n0 3 1 2 1 2\n"), Err(InstanceError::RepeatedMachine { line: 2, machine: 1 })));
+        assert!(matches!(load("2 3\n0 3 1 2 2 2\n"), Err(InstanceError::MissingJobs { line: 2, expected: 2, found: 1 })));
+        assert!(matches!(Instance::from_reader("test", TAILLARD.replace("\nMachines", "\nJobs").as_bytes(), InstanceType::TAILLARD),
+            Err(InstanceError::MissingSection { line: 6, .. })));
+        assert!(matches!(load(&TAILLARD.replace("3 1 2\n", "3 0 2\n")), Err(InstanceError::MachineOutOfRange { line: 8, machine: 0, .. })));
+        assert!(matches!(Instance::load("instances/missing.txt"), Err(InstanceError::MissingFile { .. })));
+    }
+
+    #[test]
+    fn looks_up_best_known_makespans() {
+        assert_eq!(best_known("ft06"), Some(55));
+        assert_eq!(best_known("ABZ7"), Some(656));
+        assert_eq!(best_known("ta01"), None);
+        assert_eq!(Instance::load_reader("la01", ORLIB.as_bytes()).unwrap().best_known, Some(666));
+        assert_eq!(Instance::load_reader("la01", TAILLARD.as_bytes()).unwrap().best_known, Some(11));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::jssp::{BlackBox, RepresentationMapping, CandidateSchedule};
+use crate::jssp::{BlackBox, CandidateSchedule, Decoder, RepresentationMapping, SemiActive};
 use std::cmp::{Ordering};
 use serde::{Serialize, Serializer};
 use serde::ser::SerializeStruct;
@@ -11,21 +11,49 @@ pub struct Candidate {
 }
 
 impl Candidate {
-    pub fn new(order: &Vec<usize>, process: &mut BlackBox) -> Self {
-        let sol = process.map(&order);
-        Self { order: order.clone(), makespan: process.find_makespan(&sol), schedule: sol.schedule }
+    /// With `lazy_schedule` only the makespan is computed and the schedule is left empty until `materialize`.
+    pub fn new(order: Vec<usize>, process: &mut BlackBox) -> Self {
+        process.evaluations += 1;
+        if process.config.lazy_schedule {
+            let makespan = (process.mapping)(process, &order, None);
+            return Self { order, makespan, schedule: vec![] };
+        }
+
+        let mut sol = CandidateSchedule::new(process.instance.m, process.instance.n);
+        let makespan = (process.mapping)(process, &order, Some(&mut sol));
+        Self { order, makespan, schedule: sol.schedule }
+    }
+
+    /// Decodes `order` semi-actively whatever the configured decoder, so the makespan is the one of the order's
+    /// disjunctive graph. Under another decoder the schedule is always built, as `materialize` would use that decoder.
+    pub fn semi_active(order: Vec<usize>, process: &mut BlackBox) -> Self {
+        if process.config.decoder == Decoder::SemiActive { return Self::new(order, process); }
+
+        process.evaluations += 1;
+        let mut sol = CandidateSchedule::new(process.instance.m, process.instance.n);
+        let makespan = <BlackBox as RepresentationMapping<SemiActive>>::map(process, &order, Some(&mut sol));
+        Self { order, makespan, schedule: sol.schedule }
+    }
+
+    pub fn materialize(&mut self, process: &mut BlackBox) {
+        if !self.schedule.is_empty() { return; }
+
+        let mut sol = CandidateSchedule::new(process.instance.m, process.instance.n);
+        (process.mapping)(process, &self.order, Some(&mut sol));
+        self.schedule = sol.schedule;
     }
 }
 
 impl PartialOrd for Candidate {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        other.makespan.partial_cmp(&self.makespan)
+        Some(self.cmp(other))
     }
 }
 
+/// Shorter makespans order greater, so `a > b` reads as `a` being the better candidate.
 impl Ord for Candidate {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.makespan.cmp(&other.makespan)
+        other.makespan.cmp(&self.makespan)
     }
 }
 
@@ -0,0 +1,64 @@
+use crate::jssp::*;
+use crate::jssp::termination::Termination;
+use std::path::PathBuf;
+
+#[derive(Clone)]
+pub struct RunConfig {
+    pub termination: Termination,
+    pub seed: Option<u64>,
+    pub decoder: Decoder,
+    pub history_interval: f64,
+    pub output: Option<PathBuf>,
+    pub label: Option<String>,
+    /// Compute only makespans while searching and decode schedules for the candidates kept as the best.
+    /// On by default, as an eager schedule is allocated or copied for every evaluation.
+    pub lazy_schedule: bool,
+}
+
+impl RunConfig {
+    pub fn new(termination: Termination) -> Self {
+        Self {
+            termination,
+            seed: None,
+            decoder: Decoder::SemiActive,
+            history_interval: 0.01,
+            output: None,
+            label: None,
+            lazy_schedule: true,
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_decoder(mut self, decoder: Decoder) -> Self {
+        self.decoder = decoder;
+        self
+    }
+
+    pub fn with_history_interval(mut self, history_interval: f64) -> Self {
+        self.history_interval = history_interval;
+        self
+    }
+
+    pub fn with_output<P: Into<PathBuf>>(mut self, output: P) -> Self {
+        self.output = Some(output.into());
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(String::from(label));
+        self
+    }
+
+    pub fn with_lazy_schedule(mut self, lazy_schedule: bool) -> Self {
+        self.lazy_schedule = lazy_schedule;
+        self
+    }
+}
+
+impl Default for RunConfig {
+    fn default() -> Self { Self::new(Termination::seconds(10.0)) }
+}
@@ -0,0 +1,191 @@
+use crate::jssp::*;
+use crate::jssp::results::RunResult;
+use crate::jssp::gantt::colour;
+use std::fmt::Write as FmtWrite;
+
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 240.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 50.0;
+
+fn nice_step(range: f64) -> f64 {
+    let rough = (range / 8.0).max(f64::MIN_POSITIVE);
+    let magnitude = 10f64.powf(rough.log10().floor());
+    [1.0, 2.0, 5.0, 10.0].iter().map(|x| x * magnitude).find(|&x| x >= rough).unwrap()
+}
+
+fn format_tick(value: f64, step: f64) -> String {
+    let decimals = if step >= 1.0 { 0 } else { (-step.log10()).ceil() as usize };
+    format!("{:.*}", decimals, value)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Series are coloured per instance and algorithm; repeated runs share a colour and a legend entry.
+fn series_label(results: &[RunResult], result: &RunResult) -> String {
+    if results.iter().all(|x| x.instance == result.instance) { result.algorithm.clone() } else { format!("{} {}", result.instance, result.algorithm) }
+}
+
+/// Draws best makespan over time for every run as a step curve, with dashed lines at the lower
+/// bounds and best known makespans. With `log_time` the time axis is logarithmic.
+pub fn svg(results: &[RunResult], width: usize, height: usize, log_time: bool) -> String {
+    let labels = results.iter().map(|x| series_label(results, x)).collect_vec();
+    let legend = labels.iter().unique().cloned().collect_vec();
+
+    let end = |x: &RunResult| x.history.last().map_or(0.0, |y| y.0).max(x.seconds);
+    let t_max = results.iter().map(end).fold(0.0, f64::max).max(1e-6);
+    let t_min = results.iter().flat_map(|x| x.history.iter().map(|y| y.0)).filter(|&x| x > 0.0)
+        .fold(t_max, f64::min).min(t_max / 10.0);
+
+    let references = results.iter().map(|x| (x.lower_bound, "lower bound"))
+        .chain(results.iter().filter_map(|x| x.best_known.map(|y| (y, "best known"))))
+        .unique().sorted().chunk_by(|x| x.0).into_iter()
+        .map(|(makespan, kinds)| (makespan, kinds.map(|x| x.1).join(" = ")))
+        .collect_vec();
+
+    let makespans = results.iter().flat_map(|x| x.history.iter().map(|y| y.1)).chain(references.iter().map(|x| x.0)).collect_vec();
+    let (low, high) = (*makespans.iter().min().unwrap_or(&0) as f64, *makespans.iter().max().unwrap_or(&1) as f64);
+    let padding = ((high - low) * 0.05).max(1.0);
+    let (y_min, y_max) = (low - padding, high + padding);
+
+    let (plot_width, plot_height) = (width as f64 - MARGIN_LEFT - MARGIN_RIGHT, height as f64 - MARGIN_TOP - MARGIN_BOTTOM);
+    let x = |time: f64| if log_time {
+        MARGIN_LEFT + (time.max(t_min).log10() - t_min.log10()) / (t_max.log10() - t_min.log10()) * plot_width
+    } else {
+        MARGIN_LEFT + time / t_max * plot_width
+    };
+    let y = |makespan: f64| MARGIN_TOP + (y_max - makespan) / (y_max - y_min) * plot_height;
+    let (right, bottom) = (MARGIN_LEFT + plot_width, MARGIN_TOP + plot_height);
+
+    let mut out = String::new();
+    writeln!(out, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" font-family="sans-serif" font-size="11">"#,
+             width, height, width, height).unwrap();
+    writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+    writeln!(out, r#"<text x="{}" y="24" font-size="14">Convergence</text>"#, MARGIN_LEFT).unwrap();
+
+    let time_ticks = if log_time {
+        (t_min.log10().ceil() as i32..=t_max.log10().floor() as i32).map(|x| (10f64.powi(x), format!("{}", 10f64.powi(x)))).collect_vec()
+    } else {
+        let step = nice_step(t_max);
+        (0..).map(|i| i as f64 * step).take_while(|&x| x <= t_max).map(|x| (x, format_tick(x, step))).collect_vec()
+    };
+    for (time, label) in time_ticks {
+        writeln!(out, r#"<line x1="{:.1}" y1="{}" x2="{:.1}" y2="{:.1}" stroke="gainsboro"/>"#, x(time), MARGIN_TOP, x(time), bottom).unwrap();
+        writeln!(out, r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#, x(time), bottom + 16.0, label).unwrap();
+    }
+    let step = nice_step(y_max - y_min);
+    for makespan in (0..).map(|i| (y_min / step).ceil() * step + i as f64 * step).take_while(|&x| x <= y_max) {
+        writeln!(out, r#"<line x1="{}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="gainsboro"/>"#, MARGIN_LEFT, y(makespan), right, y(makespan)).unwrap();
+        writeln!(out, r#"<text x="{}" y="{:.1}" text-anchor="end" dominant-baseline="middle">{}</text>"#,
+                 MARGIN_LEFT - 6.0, y(makespan), format_tick(makespan, step)).unwrap();
+    }
+    writeln!(out, r#"<rect x="{}" y="{}" width="{:.1}" height="{:.1}" fill="none" stroke="black"/>"#, MARGIN_LEFT, MARGIN_TOP, plot_width, plot_height).unwrap();
+    writeln!(out, r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">time [s]{}</text>"#, MARGIN_LEFT + plot_width / 2.0, bottom + 36.0,
+             if log_time { " (log scale)" } else { "" }).unwrap();
+    writeln!(out, r#"<text x="16" y="{:.1}" text-anchor="middle" transform="rotate(-90 16 {:.1})">makespan</text>"#,
+             MARGIN_TOP + plot_height / 2.0, MARGIN_TOP + plot_height / 2.0).unwrap();
+
+    for (makespan, kind) in references.iter() {
+        let stroke = if kind.contains("best known") { "crimson" } else { "black" };
+        writeln!(out, r#"<line x1="{}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-dasharray="6 4"/>"#,
+                 MARGIN_LEFT, y(*makespan as f64), right, y(*makespan as f64), stroke).unwrap();
+        writeln!(out, r#"<text x="{:.1}" y="{:.1}" text-anchor="end" fill="{}">{} {}</text>"#,
+                 right - 4.0, y(*makespan as f64) - 4.0, stroke, kind, makespan).unwrap();
+    }
+
+    for (result, label) in results.iter().zip(labels.iter()) {
+        let mut points = result.history.iter();
+        let first = match points.next() {
+            Some(first) => first,
+            None => continue,
+        };
+        let mut path = format!("M{:.1} {:.1}", x(first.0), y(first.1 as f64));
+        for (time, makespan) in points {
+            write!(path, " H{:.1} V{:.1}", x(*time), y(*makespan as f64)).unwrap();
+        }
+        write!(path, " H{:.1}", x(end(result))).unwrap();
+
+        let index = legend.iter().position(|x| x == label).unwrap();
+        writeln!(out, r#"<path d="{}" fill="none" stroke="{}" stroke-width="1.5" opacity="0.8"><title>{}: {}</title></path>"#,
+                 path, colour(index), escape(label), result.makespan).unwrap();
+    }
+
+    for (index, label) in legend.iter().enumerate() {
+        let top = MARGIN_TOP + index as f64 * 18.0;
+        writeln!(out, r#"<rect x="{:.1}" y="{:.1}" width="14" height="4" fill="{}"/>"#, right + 16.0, top + 4.0, colour(index)).unwrap();
+        writeln!(out, r#"<text x="{:.1}" y="{:.1}" dominant-baseline="middle">{}</text>"#, right + 36.0, top + 6.0, escape(label)).unwrap();
+    }
+    writeln!(out, "</svg>").unwrap();
+    out
+}
+
+pub fn html(results: &[RunResult], width: usize, height: usize, log_time: bool) -> String {
+    format!("<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Convergence</title></head>\n<body>\n{}</body>\n</html>\n",
+            svg(results, width, height, log_time))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::convergence::*;
+
+    fn result(algorithm: &str, seconds: f64, history: Vec<(f64, usize)>) -> RunResult {
+        RunResult {
+            instance: String::from("la01"), algorithm: String::from(algorithm), makespan: history.last().unwrap().1,
+            lower_bound: 600, best_known: Some(666), iterations: 10, evaluations: 10, seconds, history,
+        }
+    }
+
+    #[test]
+    fn draws_a_step_curve_per_run_and_a_legend_entry_per_algorithm() {
+        let results = [result("rs", 2.0, vec![(0.0, 900), (0.5, 800), (1.5, 700)]),
+                       result("rs", 1.0, vec![(0.0, 950), (0.2, 750)]),
+                       result("ts", 4.0, vec![(0.0, 850), (3.0, 670)])];
+        let out = svg(&results, 900, 500, false);
+
+        assert_well_formed(&out);
+        assert_eq!(out.matches("<path ").count(), 3);
+        assert_eq!(out.matches(r#"height="4""#).count(), 2);
+        assert_eq!(out.matches("<title>rs: ").count(), 2);
+        assert!(out.contains(&format!(" H{:.1}\"", 900.0 - MARGIN_RIGHT)));
+        assert_eq!(out.matches(r#"stroke-dasharray="6 4""#).count(), 2);
+        assert!(out.contains("lower bound 600") && out.contains("best known 666"));
+        assert!(!out.contains("log scale"));
+    }
+
+    #[test]
+    fn merges_equal_references_and_labels_log_time() {
+        let mut run = result("rs <1>", 2.0, vec![(0.01, 900), (1.0, 700)]);
+        run.lower_bound = 666;
+        let out = svg(&[run.clone()], 900, 500, true);
+
+        assert_well_formed(&out);
+        assert_eq!(out.matches(r#"stroke-dasharray="6 4""#).count(), 1);
+        assert!(out.contains("best known = lower bound 666"));
+        assert!(out.contains("time [s] (log scale)"));
+        assert!(out.contains("rs &lt;1&gt;") && !out.contains("rs <1>"));
+
+        let page = html(&[run], 900, 500, true);
+        assert!(page.starts_with("<!DOCTYPE html>") && page.contains(&out) && page.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn saved_runs_load_back_as_results() {
+        let mut process = test_process(3);
+        process.config.label = Some(String::from("seeded"));
+        let candidate = <BlackBox as NullaryOperator>::apply(&mut process);
+        process.update(&candidate);
+        let process = process.finalize();
+
+        let run = RunResult::from_json(&serde_json::to_string(&process).unwrap()).unwrap();
+        assert_eq!(run.instance, process.instance.name);
+        assert_eq!(run.algorithm, "seeded");
+        assert_eq!(run.makespan, process.best_candidate.makespan);
+        assert_eq!(run.lower_bound, process.lower_bound);
+        assert_eq!((run.iterations, run.evaluations), (process.termination_counter, process.evaluations));
+        assert_eq!(run.history, process.history);
+        assert_eq!(run.seconds, process.seconds());
+    }
+}
@@ -0,0 +1,208 @@
+use crate::jssp::*;
+use crate::jssp::results::{RunResult, csv};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Target {
+    Makespan(usize),
+    /// Relative gap to the best known makespan, or to the lower bound when none is known.
+    Gap(f64),
+}
+
+impl Target {
+    pub fn makespan(&self, result: &RunResult) -> usize {
+        match *self {
+            Target::Makespan(makespan) => makespan,
+            Target::Gap(gap) => (result.best_known.unwrap_or(result.lower_bound) as f64 * (1.0 + gap)).floor() as usize,
+        }
+    }
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Makespan(makespan) => write!(f, "{}", makespan),
+            Target::Gap(gap) => write!(f, "{}%", gap * 100.0),
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let target = match s.strip_suffix('%') {
+            Some(percent) => percent.parse::<f64>().ok().map(|x| Target::Gap(x / 100.0)),
+            None => s.parse::<usize>().ok().map(Target::Makespan),
+        };
+        target.ok_or(format!("Expected a makespan or a percentage gap, found {}", s))
+    }
+}
+
+impl Serialize for Target {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// First time at which the run's best makespan reached the target.
+pub fn time_to_target(history: &[(f64, usize)], target: usize) -> Option<f64> {
+    history.iter().find(|(_, makespan)| *makespan <= target).map(|(time, _)| *time)
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Ecdf {
+    pub instance: String,
+    pub algorithm: String,
+    pub target: Target,
+    pub runs: usize,
+    /// `(seconds, fraction of runs that reached the target by then)`, one step per distinct hitting time.
+    pub points: Vec<(f64, f64)>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Ert {
+    pub instance: String,
+    pub algorithm: String,
+    pub target: Target,
+    pub runs: usize,
+    pub successes: usize,
+    /// Time spent by all runs (unsuccessful ones count in full) divided by the successes.
+    pub seconds: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct RuntimeAnalysis {
+    pub ecdfs: Vec<Ecdf>,
+    pub erts: Vec<Ert>,
+}
+
+impl RuntimeAnalysis {
+    pub fn new(results: &[RunResult], targets: &[Target]) -> Self {
+        let mut grouped: BTreeMap<(String, String), Vec<&RunResult>> = BTreeMap::new();
+        for result in results {
+            grouped.entry((result.instance.clone(), result.algorithm.clone())).or_default().push(result);
+        }
+
+        let mut ecdfs = Vec::new();
+        let mut erts = Vec::new();
+        for ((instance, algorithm), runs) in grouped.iter() {
+            for &target in targets {
+                let hits = runs.iter().map(|x| time_to_target(&x.history, target.makespan(x))).collect_vec();
+                let mut times = hits.iter().flatten().cloned().collect_vec();
+                times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+                let mut points: Vec<(f64, f64)> = Vec::new();
+                for (i, time) in times.iter().enumerate() {
+                    let fraction = (i + 1) as f64 / runs.len() as f64;
+                    match points.last_mut() {
+                        Some(last) if last.0 == *time => last.1 = fraction,
+                        _ => points.push((*time, fraction)),
+                    }
+                }
+
+                let spent = runs.iter().zip(hits.iter()).map(|(run, hit)| hit.unwrap_or(run.seconds)).sum::<f64>();
+                erts.push(Ert {
+                    instance: instance.clone(),
+                    algorithm: algorithm.clone(),
+                    target,
+                    runs: runs.len(),
+                    successes: times.len(),
+                    seconds: if times.is_empty() { None } else { Some(spent / times.len() as f64) },
+                });
+                ecdfs.push(Ecdf { instance: instance.clone(), algorithm: algorithm.clone(), target, runs: runs.len(), points });
+            }
+        }
+        Self { ecdfs, erts }
+    }
+
+    pub fn ecdf_to_csv(&self) -> String {
+        let mut out = String::from("instance,algorithm,target,seconds,fraction\n");
+        for ecdf in self.ecdfs.iter() {
+            for (time, fraction) in ecdf.points.iter() {
+                out += &format!("{},{},{},{},{}\n", ecdf.instance, csv(&ecdf.algorithm), ecdf.target, time, fraction);
+            }
+        }
+        out
+    }
+
+    pub fn ert_to_csv(&self) -> String {
+        let mut out = String::from("instance,algorithm,target,runs,successes,ert\n");
+        for ert in self.erts.iter() {
+            out += &format!("{},{},{},{},{},{}\n", ert.instance, csv(&ert.algorithm), ert.target, ert.runs, ert.successes,
+                            ert.seconds.map_or(String::from("inf"), |x| x.to_string()));
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::ecdf::*;
+
+    fn run(history: Vec<(f64, usize)>, seconds: f64) -> RunResult {
+        RunResult {
+            instance: String::from("tiny"), algorithm: String::from("rs"), makespan: history.last().unwrap().1,
+            lower_bound: 100, best_known: None, iterations: 0, evaluations: 0, seconds, history,
+        }
+    }
+
+    fn runs() -> Vec<RunResult> {
+        vec![
+            run(vec![(0.0, 120), (1.0, 110), (2.0, 100)], 3.0),
+            run(vec![(0.0, 105), (4.0, 100)], 5.0),
+            run(vec![(0.5, 130), (2.0, 115)], 6.0),
+        ]
+    }
+
+    fn close(a: f64, b: f64) -> bool { (a - b).abs() < 1e-9 }
+
+    #[test]
+    fn finds_the_first_time_at_the_target() {
+        let history = [(0.0, 120), (1.0, 110), (2.0, 100)];
+        assert_eq!(time_to_target(&history, 130), Some(0.0));
+        assert_eq!(time_to_target(&history, 110), Some(1.0));
+        assert_eq!(time_to_target(&history, 105), Some(2.0));
+        assert_eq!(time_to_target(&history, 99), None);
+        assert_eq!(time_to_target(&[], 1_000), None);
+    }
+
+    #[test]
+    fn parses_targets() {
+        assert_eq!("666".parse::<Target>(), Ok(Target::Makespan(666)));
+        assert_eq!("5%".parse::<Target>(), Ok(Target::Gap(0.05)));
+        assert!("five".parse::<Target>().is_err());
+        assert_eq!(Target::Gap(0.05).makespan(&runs()[0]), 105);
+    }
+
+    #[test]
+    fn ecdf_steps_at_hitting_times() {
+        let analysis = RuntimeAnalysis::new(&runs(), &[Target::Makespan(110), Target::Makespan(130)]);
+        assert_eq!(analysis.ecdfs[0].points, vec![(0.0, 1.0 / 3.0), (1.0, 2.0 / 3.0)]);
+        assert_eq!(analysis.ecdfs[1].points, vec![(0.0, 2.0 / 3.0), (0.5, 1.0)]);
+    }
+
+    #[test]
+    fn ert_counts_unsuccessful_runs_in_full() {
+        let analysis = RuntimeAnalysis::new(&runs(), &[Target::Makespan(110), Target::Gap(0.05), Target::Makespan(130)]);
+        let erts = &analysis.erts;
+        assert_eq!((erts[0].runs, erts[0].successes), (3, 2));
+        assert!(close(erts[0].seconds.unwrap(), (1.0 + 0.0 + 6.0) / 2.0));
+        assert!(close(erts[1].seconds.unwrap(), (2.0 + 0.0 + 6.0) / 2.0));
+        assert!(close(erts[2].seconds.unwrap(), 0.5 / 3.0));
+    }
+
+    #[test]
+    fn unreached_target_has_no_ert() {
+        let analysis = RuntimeAnalysis::new(&runs(), &[Target::Makespan(90)]);
+        assert_eq!(analysis.erts[0].successes, 0);
+        assert_eq!(analysis.erts[0].seconds, None);
+        assert!(analysis.ecdfs[0].points.is_empty());
+        assert!(analysis.ert_to_csv().ends_with(",3,0,inf\n"));
+    }
+}
@@ -0,0 +1,294 @@
+use crate::jssp::*;
+use crate::jssp::config::RunConfig;
+use crate::jssp::rs::RandomSample;
+use crate::jssp::hc::{check_operator, HillClimber};
+use crate::jssp::sa::{SimulatedAnnealing, Temperature};
+use crate::jssp::ga::{check_parameters, Crossover, Genetic, GeneticError};
+use crate::jssp::ts::TabuSearch;
+use crate::jssp::ils::{Acceptance, IteratedLocalSearch};
+use crate::jssp::vns::{Structure, VariableNeighbourhoodSearch};
+use crate::jssp::neighbourhood::Neighbourhood;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+custom_error! {pub RunError
+    Operator{source: OperatorError} = "{source}",
+    Genetic{source: GeneticError} = "{source}",
+}
+
+#[derive(Clone, Debug)]
+pub enum Algorithm {
+    RandomSample,
+    HillClimber { reset_threshold: usize, unary_op: String },
+    SimulatedAnnealing { annealing_speed: f64, start_temperature: f64, temperature: Temperature },
+    Genetic { crossover: Crossover, crossover_chance: f64, mu: usize, lambda: usize },
+    TabuSearch { tenure: usize, neighbourhood: Neighbourhood },
+    IteratedLocalSearch { neighbourhood: Neighbourhood, strength: usize, acceptance: Acceptance },
+    VariableNeighbourhoodSearch { structures: Vec<Structure>, patience: usize },
+}
+
+impl Algorithm {
+    pub fn name(&self) -> &str {
+        match self {
+            Algorithm::RandomSample => "rs",
+            Algorithm::HillClimber { .. } => "hc",
+            Algorithm::SimulatedAnnealing { .. } => "sa",
+            Algorithm::Genetic { .. } => "ga",
+            Algorithm::TabuSearch { .. } => "ts",
+            Algorithm::IteratedLocalSearch { .. } => "ils",
+            Algorithm::VariableNeighbourhoodSearch { .. } => "vns",
+        }
+    }
+
+    pub fn run(&self, instance: Arc<Instance>, config: RunConfig) -> Result<BlackBox, RunError> {
+        Ok(match self {
+            Algorithm::RandomSample => RandomSample::new(instance, config).solve(),
+            Algorithm::HillClimber { reset_threshold, unary_op } =>
+                HillClimber::new(instance, *reset_threshold, unary_op, config)?.solve(),
+            Algorithm::SimulatedAnnealing { annealing_speed, start_temperature, temperature } =>
+                SimulatedAnnealing::new(instance, *annealing_speed, *start_temperature, config).solve(*temperature),
+            Algorithm::Genetic { crossover, crossover_chance, mu, lambda } =>
+                Genetic::new(instance, config).solve(crossover.clone(), *crossover_chance, *mu, *lambda)?,
+            Algorithm::TabuSearch { tenure, neighbourhood } => TabuSearch::new(instance, *tenure, *neighbourhood, config).solve(),
+            Algorithm::IteratedLocalSearch { neighbourhood, strength, acceptance } =>
+                IteratedLocalSearch::new(instance, *neighbourhood, *strength, *acceptance, config).solve(),
+            Algorithm::VariableNeighbourhoodSearch { structures, patience } =>
+                VariableNeighbourhoodSearch::new(instance, structures.clone(), *patience, config).solve(),
+        })
+    }
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Algorithm::RandomSample => write!(f, "{}", self.name()),
+            Algorithm::HillClimber { reset_threshold, unary_op } =>
+                write!(f, "{}:reset_threshold={},operator={}", self.name(), reset_threshold, unary_op),
+            Algorithm::SimulatedAnnealing { annealing_speed, start_temperature, temperature } =>
+                write!(f, "{}:annealing_speed={},start_temperature={},temperature={}",
+                       self.name(), annealing_speed, start_temperature, temperature),
+            Algorithm::Genetic { crossover, crossover_chance, mu, lambda } =>
+                write!(f, "{}:crossover={},crossover_chance={},mu={},lambda={}", self.name(), crossover, crossover_chance, mu, lambda),
+            Algorithm::TabuSearch { tenure, neighbourhood } =>
+                write!(f, "{}:tenure={},neighbourhood={}", self.name(), tenure, neighbourhood),
+            Algorithm::IteratedLocalSearch { neighbourhood, strength, acceptance } =>
+                write!(f, "{}:neighbourhood={},strength={},acceptance={}", self.name(), neighbourhood, strength, acceptance),
+            Algorithm::VariableNeighbourhoodSearch { structures, patience } =>
+                write!(f, "{}:neighbourhoods={},patience={}", self.name(), structures.iter().join("+"), patience),
+        }
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, parameters) = match s.split_once(':') {
+            Some((name, parameters)) => (name, parameters),
+            None => (s, ""),
+        };
+
+        let mut algorithm = match name.to_lowercase().as_str() {
+            "rs" => Algorithm::RandomSample,
+            "hc" => Algorithm::HillClimber { reset_threshold: 1_676, unary_op: String::from("nswap") },
+            "sa" => Algorithm::SimulatedAnnealing {
+                annealing_speed: 0.001,
+                start_temperature: 30.0,
+                temperature: Temperature::Exponential,
+            },
+            "ga" => Algorithm::Genetic { crossover: Crossover::Ppx, crossover_chance: 0.5, mu: 32, lambda: 32 },
+            "ts" => Algorithm::TabuSearch { tenure: 8, neighbourhood: Neighbourhood::N5 },
+            "ils" => Algorithm::IteratedLocalSearch { neighbourhood: Neighbourhood::N1, strength: 3, acceptance: Acceptance::Better },
+            "vns" => Algorithm::VariableNeighbourhoodSearch { structures: default_structures(), patience: 200 },
+            _ => return Err(format!("Unsupported algorithm {}", name)),
+        };
+
+        for parameter in parameters.split(',').filter(|x| !x.is_empty()) {
+            let (key, value) = parameter.split_once('=')
+                .ok_or(format!("Expected key=value, found {}", parameter))?;
+
+            match (&mut algorithm, key) {
+                (Algorithm::HillClimber { reset_threshold, .. }, "reset_threshold") => *reset_threshold = parse(key, value)?,
+                (Algorithm::HillClimber { unary_op, .. }, "operator") => {
+                    check_operator(value).map_err(|x| x.to_string())?;
+                    *unary_op = String::from(value)
+                }
+                (Algorithm::SimulatedAnnealing { annealing_speed, .. }, "annealing_speed") => *annealing_speed = parse(key, value)?,
+                (Algorithm::SimulatedAnnealing { start_temperature, .. }, "start_temperature") => *start_temperature = parse(key, value)?,
+                (Algorithm::SimulatedAnnealing { temperature, .. }, "temperature") => *temperature = value.parse()?,
+                (Algorithm::Genetic { crossover, .. }, "crossover") => *crossover = value.parse()?,
+                (Algorithm::Genetic { crossover_chance, .. }, "crossover_chance") => *crossover_chance = parse(key, value)?,
+                (Algorithm::Genetic { mu, .. }, "mu") => *mu = parse(key, value)?,
+                (Algorithm::Genetic { lambda, .. }, "lambda") => *lambda = parse(key, value)?,
+                (Algorithm::TabuSearch { tenure, .. }, "tenure") => *tenure = parse(key, value)?,
+                (Algorithm::TabuSearch { neighbourhood, .. }, "neighbourhood") => *neighbourhood = value.parse()?,
+                (Algorithm::IteratedLocalSearch { neighbourhood, .. }, "neighbourhood") => *neighbourhood = value.parse()?,
+                (Algorithm::IteratedLocalSearch { strength, .. }, "strength") => *strength = parse(key, value)?,
+                (Algorithm::IteratedLocalSearch { acceptance, .. }, "acceptance") => *acceptance = value.parse()?,
+                (Algorithm::VariableNeighbourhoodSearch { structures, .. }, "neighbourhoods") =>
+                    *structures = value.split('+').map(|x| x.parse()).collect::<Result<_, _>>()?,
+                (Algorithm::VariableNeighbourhoodSearch { patience, .. }, "patience") => *patience = parse(key, value)?,
+                _ => return Err(format!("Unsupported parameter {} for {}", key, name)),
+            }
+        }
+        if let Algorithm::Genetic { crossover_chance, mu, lambda, .. } = algorithm {
+            check_parameters(crossover_chance, mu, lambda).map_err(|x| x.to_string())?;
+        }
+        Ok(algorithm)
+    }
+}
+
+/// Critical-block neighbourhoods first, as they are the cheapest to exhaust, then the random operators.
+pub fn default_structures() -> Vec<Structure> {
+    ["n5", "n1", "n6", "1swap", "insertion", "nswap"].iter().map(|x| x.parse().unwrap()).collect()
+}
+
+fn parse<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid value {} for {}", value, key))
+}
+
+#[derive(Clone, Serialize)]
+pub struct RunSummary {
+    pub instance: String,
+    pub algorithm: String,
+    pub repetition: usize,
+    pub seed: u64,
+    pub makespan: usize,
+    pub lower_bound: usize,
+    pub gap: Option<f64>,
+    pub iterations: usize,
+    pub evaluations: usize,
+    pub seconds: f64,
+    pub path: PathBuf,
+}
+
+#[derive(Serialize)]
+struct GroupSummary {
+    instance: String,
+    algorithm: String,
+    runs: usize,
+    best: usize,
+    mean: f64,
+    worst: usize,
+}
+
+pub struct Experiment {
+    instances: Vec<Arc<Instance>>,
+    algorithms: Vec<Algorithm>,
+    repetitions: usize,
+    config: RunConfig,
+    output: PathBuf,
+}
+
+impl Experiment {
+    pub fn new(instances: Vec<Arc<Instance>>, algorithms: Vec<Algorithm>, repetitions: usize,
+               config: RunConfig, output: PathBuf) -> Self {
+        Self { instances, algorithms, repetitions, config, output }
+    }
+
+    pub fn run(&self) -> std::io::Result<Vec<RunSummary>> {
+        std::fs::create_dir_all(&self.output)?;
+
+        let runs = self.instances.iter()
+            .cartesian_product(self.algorithms.iter().enumerate())
+            .cartesian_product(0..self.repetitions)
+            .map(|((instance, (index, algorithm)), repetition)| (instance.clone(), index, algorithm.clone(), repetition))
+            .collect_vec();
+        let seeds = derive_seeds(self.config.seed.unwrap_or_else(|| StdRng::from_entropy().gen()), runs.len());
+
+        let next = AtomicUsize::new(0);
+        let thread_count = thread::available_parallelism().map(|x| x.get()).unwrap_or(1).min(runs.len().max(1));
+        let results: Vec<std::io::Result<Vec<(usize, RunSummary)>>> = thread::scope(|scope| {
+            let handles = (0..thread_count).map(|_| scope.spawn(|| {
+                let mut summaries = Vec::new();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= runs.len() { return Ok(summaries); }
+
+                    let (instance, index, algorithm, repetition) = &runs[i];
+                    let config = RunConfig {
+                        seed: Some(seeds[i]),
+                        output: None,
+                        label: Some(algorithm.to_string()),
+                        ..self.config.clone()
+                    };
+                    let process = algorithm.run(instance.clone(), config)
+                        .map_err(|x| std::io::Error::new(std::io::ErrorKind::InvalidInput, x.to_string()))?;
+
+                    let path = self.output.join(format!("{}-{}{}-r{}.json", instance.name, algorithm.name(), index, repetition));
+                    process.save_to(&path)?;
+                    summaries.push((i, RunSummary {
+                        instance: instance.name.clone(),
+                        algorithm: algorithm.to_string(),
+                        repetition: *repetition,
+                        seed: seeds[i],
+                        makespan: process.best_candidate.makespan,
+                        lower_bound: process.lower_bound,
+                        gap: process.gap(),
+                        iterations: process.termination_counter,
+                        evaluations: process.evaluations,
+                        seconds: process.seconds(),
+                        path,
+                    }));
+                }
+            })).collect_vec();
+            handles.into_iter().map(|x| x.join().expect("Failed to join the experiment thread")).collect()
+        });
+
+        let mut summaries = Vec::with_capacity(runs.len());
+        for result in results { summaries.extend(result?); }
+        summaries.sort_by_key(|(i, _)| *i);
+        let summaries = summaries.into_iter().map(|(_, x)| x).collect_vec();
+
+        self.save_summary(&summaries)?;
+        Ok(summaries)
+    }
+
+    fn save_summary(&self, summaries: &Vec<RunSummary>) -> std::io::Result<()> {
+        let mut groups: BTreeMap<(String, String), Vec<usize>> = BTreeMap::new();
+        for summary in summaries {
+            groups.entry((summary.instance.clone(), summary.algorithm.clone())).or_default().push(summary.makespan);
+        }
+        let groups = groups.into_iter().map(|((instance, algorithm), makespans)| GroupSummary {
+            instance,
+            algorithm,
+            runs: makespans.len(),
+            best: *makespans.iter().min().unwrap(),
+            mean: makespans.iter().sum::<usize>() as f64 / makespans.len() as f64,
+            worst: *makespans.iter().max().unwrap(),
+        }).collect_vec();
+
+        #[derive(Serialize)]
+        struct Summary<'a> {
+            runs: &'a Vec<RunSummary>,
+            groups: Vec<GroupSummary>,
+        }
+
+        let data = serde_json::to_string_pretty(&Summary { runs: summaries, groups })?;
+        let mut fp = File::create(self.output.join("summary.json"))?;
+        write!(fp, "{}", data)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::experiment::Algorithm;
+
+    #[test]
+    fn seeded_runs_repeat() {
+        let instance = test_instance();
+        for name in ["rs", "hc", "hc:operator=n5", "sa", "ga:mu=8,lambda=8", "ts", "ils", "vns"].iter() {
+            let algorithm = name.parse::<Algorithm>().unwrap();
+            let run = |seed| algorithm.run(instance.clone(), RunConfig::new(Termination::evaluations(1_000)).with_seed(seed)).unwrap();
+
+            let first = outcome(&run(7));
+            assert_eq!(first, outcome(&run(7)), "{}", name);
+            assert_ne!(first.0, outcome(&run(8)).0, "{}", name);
+        }
+    }
+}
@@ -1,18 +1,108 @@
 use crate::jssp::*;
 use crate::jssp::can::Candidate;
 use std::mem::swap;
+use std::str::FromStr;
+
+/// Recombination of parents chosen among the cleared population.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Crossover {
+    /// Precedence preservative crossover with a uniform random mask.
+    Ppx,
+    /// Job-based order crossover: a random subset of jobs keeps its positions from the first parent.
+    Jox,
+    /// Generalized order crossover: a substring of the second parent is implanted into the first.
+    Gox,
+    /// Precedence preservative crossover over the given number of parents.
+    MultiPpx(usize),
+    /// Precedence preservative crossover with a fixed mask of parent indices, repeated to the order length.
+    /// Takes as many parents as the largest index needs; indices beyond the parents given wrap around.
+    MaskedPpx(Vec<usize>),
+}
+
+impl Crossover {
+    pub fn parents(&self) -> usize {
+        match *self {
+            Crossover::MultiPpx(parents) => parents,
+            Crossover::MaskedPpx(ref mask) => max(mask.iter().max().map_or(0, |x| x + 1), 2),
+            _ => 2,
+        }
+    }
+
+    pub fn apply(&self, process: &mut BlackBox, parents: &[&Candidate]) -> Candidate {
+        match self {
+            Crossover::Ppx => <BlackBox as BinaryOperator>::apply(process, parents[0], parents[1]),
+            Crossover::Jox => <BlackBox as BinaryOperatorJox>::apply(process, parents[0], parents[1]),
+            Crossover::Gox => <BlackBox as BinaryOperatorGox>::apply(process, parents[0], parents[1]),
+            Crossover::MultiPpx(_) => <BlackBox as MultiParentOperator>::apply(process, parents),
+            Crossover::MaskedPpx(mask) => {
+                let orders = parents.iter().map(|x| x.order.as_slice()).collect_vec();
+                let mask = mask.iter().map(|x| x % orders.len()).cycle().take(orders[0].len()).collect_vec();
+                Candidate::new(ppx(&orders, &mask), process)
+            }
+        }
+    }
+}
+
+impl Display for Crossover {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Crossover::Ppx => write!(f, "ppx"),
+            Crossover::Jox => write!(f, "jox"),
+            Crossover::Gox => write!(f, "gox"),
+            Crossover::MultiPpx(parents) => write!(f, "mppx{}", parents),
+            Crossover::MaskedPpx(mask) => write!(f, "ppx:{}", mask.iter().join("")),
+        }
+    }
+}
+
+impl FromStr for Crossover {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        match s.as_str() {
+            "ppx" => Ok(Crossover::Ppx),
+            "jox" => Ok(Crossover::Jox),
+            "gox" => Ok(Crossover::Gox),
+            _ => if let Some(mask) = s.strip_prefix("ppx:") {
+                let mask = mask.chars().map(|x| x.to_digit(10).map(|x| x as usize)).collect::<Option<Vec<_>>>();
+                match mask {
+                    Some(mask) if !mask.is_empty() => Ok(Crossover::MaskedPpx(mask)),
+                    _ => Err(format!("Unsupported mask in {}, expected parent indices such as ppx:0110", s)),
+                }
+            } else {
+                match s.strip_prefix("mppx").map(|x| x.parse::<usize>()) {
+                    Some(Ok(parents)) if parents >= 2 => Ok(Crossover::MultiPpx(parents)),
+                    _ => Err(format!("Unsupported crossover {}, expected one of ppx, jox, gox, mppx<parents>, ppx:<mask>", s)),
+                }
+            },
+        }
+    }
+}
+
+custom_error! {pub GeneticError
+    EmptyPopulation{mu: usize, lambda: usize} = "Expected mu and lambda of at least 1, got mu {mu} and lambda {lambda}",
+    CrossoverChance{chance: f64} = "Expected a crossover chance within [0, 1], got {chance}",
+}
+
+/// Rejects parameters that would panic in the offspring loop or never evaluate a candidate.
+pub fn check_parameters(crossover_chance: f64, mu: usize, lambda: usize) -> Result<(), GeneticError> {
+    if mu == 0 || lambda == 0 { return Err(GeneticError::EmptyPopulation { mu, lambda }); }
+    if !(0.0..=1.0).contains(&crossover_chance) { return Err(GeneticError::CrossoverChance { chance: crossover_chance }); }
+    Ok(())
+}
 
 pub struct Genetic {
     process: BlackBox,
 }
 
 impl Genetic {
-    pub fn new(instance: &Instance) -> Self {
+    pub fn new(instance: Arc<Instance>, config: RunConfig) -> Self {
         Self {
-            process: BlackBox::new(instance.clone(), String::from("Genetic with clearing")),
+            process: BlackBox::new(instance, String::from("Genetic with clearing"), config),
         }
     }
-    fn find_clear_length(&mut self, p: &mut Vec<Candidate>, mu: usize) -> usize {
+    fn find_clear_length(&mut self, p: &mut [Candidate], mu: usize) -> usize {
         let mut last_makespan = usize::MIN;
 
         let mut unique_count = 0;
@@ -29,12 +119,13 @@ impl Genetic {
         unique_count
     }
 
-    pub fn solve(&mut self, crossover_chance: f64, mu: usize, lambda: usize) -> BlackBox {
+    pub fn solve(&mut self, crossover: Crossover, crossover_chance: f64, mu: usize, lambda: usize) -> Result<BlackBox, GeneticError> {
+        check_parameters(crossover_chance, mu, lambda)?;
         let length = mu + lambda;
-        let mut candidates: Vec<Candidate> = (0..length).into_iter().map(|_|
+        let mut candidates: Vec<Candidate> = (0..length).map(|_|
             <BlackBox as NullaryOperator>::apply(&mut self.process)).collect();
 
-        while !<BlackBox as TerminationCriterion<Counter>>::should_terminate(&mut self.process) {
+        while !self.process.should_terminate() {
             candidates.sort_by_key(|x| x.makespan);
             self.process.update_history(&candidates[0]);
 
@@ -46,10 +137,14 @@ impl Genetic {
 
             let mut p1 = 0;
             for i in u..length {
-                candidates[i] = if self.process.random.gen_bool(crossover_chance) {
-                    let mut p2 = self.process.random.gen_range(0..u);
-                    while p1 == p2 { p2 = self.process.random.gen_range(0..u) }
-                    <BlackBox as BinaryOperator>::apply(&mut self.process, &candidates[p1], &candidates[p2])
+                candidates[i] = if self.process.random.gen_bool(crossover_chance) && u > 1 {
+                    let mut parents = vec![p1];
+                    while parents.len() < min(crossover.parents(), u) {
+                        let p = self.process.random.gen_range(0..u);
+                        if !parents.contains(&p) { parents.push(p); }
+                    }
+                    let parents = parents.iter().map(|&x| &candidates[x]).collect_vec();
+                    crossover.apply(&mut self.process, &parents)
                 } else { <BlackBox as UnaryOperatorNSwap>::apply(&mut self.process, &candidates[p1]) };
                 p1 = (p1 + 1) % u;
             }
@@ -57,6 +152,62 @@ impl Genetic {
 
         candidates.sort_by_key(|x| x.makespan);
         self.process.update(&candidates[0]);
-        self.process.clone()
+        Ok(self.process.clone().finalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::ga::{Crossover, Genetic};
+    use crate::jssp::experiment::Algorithm;
+
+    #[test]
+    fn crossovers_keep_job_counts() {
+        let mut process = test_process(5);
+        let parents = (0..4).map(|_| <BlackBox as NullaryOperator>::apply(&mut process)).collect_vec();
+        let parents = parents.iter().collect_vec();
+
+        let orders = [parents[0].order.as_slice(), parents[1].order.as_slice()];
+        assert_eq!(ppx(&orders, &vec![0; orders[0].len()]), parents[0].order);
+        assert_eq!(ppx(&orders, &vec![1; orders[0].len()]), parents[1].order);
+
+        let masked = Crossover::MaskedPpx(vec![0, 1]);
+        let child = masked.apply(&mut process, &parents[..2]);
+        assert_eq!(child.order, ppx(&orders, &(0..orders[0].len()).map(|x| x % 2).collect_vec()));
+        let child = Crossover::MaskedPpx(vec![0, 3]).apply(&mut process, &parents[..2]);
+        assert_eq!(child.order, ppx(&orders, &(0..orders[0].len()).map(|x| x % 2).collect_vec()));
+
+        let expected = parents[0].order.iter().copied().sorted().collect_vec();
+        for crossover in [Crossover::Ppx, Crossover::Jox, Crossover::Gox, Crossover::MultiPpx(4), Crossover::MaskedPpx(vec![2, 0, 1])].iter() {
+            for _ in 0..200 {
+                let child = crossover.apply(&mut process, &parents[..crossover.parents()]);
+                assert_eq!(child.order.iter().copied().sorted().collect_vec(), expected, "{} changes job counts", crossover);
+            }
+        }
+    }
+
+    #[test]
+    fn parses_masks() {
+        assert_eq!("ppx:0110".parse::<Crossover>(), Ok(Crossover::MaskedPpx(vec![0, 1, 1, 0])));
+        assert_eq!("ppx:021".parse::<Crossover>().unwrap().parents(), 3);
+        assert_eq!("ppx:0".parse::<Crossover>().unwrap().parents(), 2);
+        assert_eq!(Crossover::MaskedPpx(vec![1, 0]).to_string(), "ppx:10");
+        assert!("ppx:".parse::<Crossover>().is_err());
+        assert!("ppx:01x".parse::<Crossover>().is_err());
+    }
+
+    #[test]
+    fn rejects_empty_populations_and_invalid_chances() {
+        let instance = test_instance();
+        let config = RunConfig::new(Termination::evaluations(100)).with_seed(5);
+        for &(chance, mu, lambda) in [(0.5, 0, 4), (0.5, 1, 0), (2.0, 4, 4), (-0.1, 4, 4)].iter() {
+            assert!(Genetic::new(instance.clone(), config.clone()).solve(Crossover::Ppx, chance, mu, lambda).is_err());
+        }
+        assert!(Genetic::new(instance, config).solve(Crossover::Ppx, 1.0, 1, 1).is_ok());
+
+        for spec in ["ga:mu=0", "ga:lambda=0", "ga:crossover_chance=2"].iter() {
+            assert!(spec.parse::<Algorithm>().is_err(), "{} is accepted", spec);
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,185 @@
+use crate::jssp::*;
+use crate::jssp::graph::DisjunctiveGraph;
+use std::collections::HashSet;
+use std::fmt::Write as FmtWrite;
+
+const MARGIN_LEFT: usize = 60;
+const MARGIN_RIGHT: usize = 20;
+const MARGIN_TOP: usize = 40;
+const MARGIN_BOTTOM: usize = 40;
+const LANE_HEIGHT: usize = 28;
+
+pub(crate) fn operations(schedule: &CandidateSchedule) -> Vec<Vec<(usize, usize, usize)>> {
+    schedule.schedule.iter()
+        .map(|row| row.chunks_exact(3).map(|x| (x[0], x[1], x[2])).collect())
+        .collect()
+}
+
+pub(crate) fn critical_operations(instance: &Instance, schedule: &CandidateSchedule) -> HashSet<(usize, usize)> {
+    let graph = DisjunctiveGraph::new(instance, schedule);
+    graph.critical_path().into_iter().map(|x| (graph.job(x), graph.machine(x))).collect()
+}
+
+pub(crate) fn colour(index: usize) -> String {
+    format!("hsl({:.0}, 65%, 60%)", (index as f64 * 137.508) % 360.0)
+}
+
+fn tick_step(makespan: usize) -> usize {
+    let rough = max(makespan / 10, 1) as f64;
+    let magnitude = 10f64.powf(rough.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0].iter().map(|x| x * magnitude).find(|&x| x >= rough).unwrap();
+    step as usize
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Renders one lane per machine and one colour per job; with `critical_path` the operations on
+/// the critical path of the schedule's disjunctive graph are outlined.
+pub fn svg(instance: &Instance, schedule: &CandidateSchedule, width: usize, critical_path: bool) -> String {
+    let machines = operations(schedule);
+    let makespan = machines.iter().flatten().map(|x| x.2).max().unwrap_or(0);
+    let critical = if critical_path { critical_operations(instance, schedule) } else { HashSet::new() };
+
+    let plot_width = width.saturating_sub(MARGIN_LEFT + MARGIN_RIGHT).max(1);
+    let height = MARGIN_TOP + machines.len() * LANE_HEIGHT + MARGIN_BOTTOM;
+    let x = |time: usize| MARGIN_LEFT as f64 + time as f64 * plot_width as f64 / max(makespan, 1) as f64;
+    let axis = MARGIN_TOP + machines.len() * LANE_HEIGHT;
+
+    let mut out = String::new();
+    writeln!(out, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" font-family="sans-serif" font-size="11">"#,
+             width, height, width, height).unwrap();
+    writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+    writeln!(out, r#"<text x="{}" y="24" font-size="14">{} (makespan {})</text>"#, MARGIN_LEFT, escape(&instance.name), makespan).unwrap();
+
+    let step = tick_step(makespan);
+    for time in (0..=makespan).step_by(step) {
+        writeln!(out, r#"<line x1="{:.1}" y1="{}" x2="{:.1}" y2="{}" stroke="gainsboro"/>"#, x(time), MARGIN_TOP, x(time), axis).unwrap();
+        writeln!(out, r#"<text x="{:.1}" y="{}" text-anchor="middle">{}</text>"#, x(time), axis + 16, time).unwrap();
+    }
+    writeln!(out, r#"<line x1="{}" y1="{}" x2="{:.1}" y2="{}" stroke="black"/>"#, MARGIN_LEFT, axis, x(makespan), axis).unwrap();
+    writeln!(out, r#"<text x="{:.1}" y="{}" text-anchor="middle">time</text>"#, x(makespan / 2), axis + 32).unwrap();
+
+    for (machine, row) in machines.iter().enumerate() {
+        let y = MARGIN_TOP + machine * LANE_HEIGHT;
+        writeln!(out, r#"<text x="{}" y="{}" text-anchor="end" dominant-baseline="middle">M{}</text>"#,
+                 MARGIN_LEFT - 8, y + LANE_HEIGHT / 2, machine).unwrap();
+
+        for &(job, start, end) in row.iter() {
+            let (left, right) = (x(start), x(end));
+            let stroke = if critical.contains(&(job, machine)) { r#"stroke="crimson" stroke-width="3""# } else { r#"stroke="dimgray" stroke-width="0.5""# };
+            writeln!(out, r#"<rect x="{:.1}" y="{}" width="{:.1}" height="{}" fill="{}" {}><title>job {} on M{}: {}-{}</title></rect>"#,
+                     left, y + 3, right - left, LANE_HEIGHT - 6, colour(job), stroke, job, machine, start, end).unwrap();
+            if right - left >= 14.0 {
+                writeln!(out, r#"<text x="{:.1}" y="{}" text-anchor="middle" dominant-baseline="middle">{}</text>"#,
+                         (left + right) / 2.0, y + LANE_HEIGHT / 2, job).unwrap();
+            }
+        }
+    }
+    writeln!(out, "</svg>").unwrap();
+    out
+}
+
+/// Renders one row per machine scaled to `width` columns, each block starting with its job number,
+/// followed by the machine's idle share of the makespan.
+pub fn ascii(schedule: &CandidateSchedule, width: usize) -> String {
+    let machines = operations(schedule);
+    let makespan = machines.iter().flatten().map(|x| x.2).max().unwrap_or(0);
+    let label_width = format!("M{}", machines.len().saturating_sub(1)).len();
+    let columns = width.saturating_sub(label_width + 14).max(10);
+    let column = |time: usize| time * columns / max(makespan, 1);
+
+    let mut out = String::new();
+    for (machine, row) in machines.iter().enumerate() {
+        let mut line = vec![' '; columns];
+        for &(job, start, end) in row.iter() {
+            let (first, last) = (column(start), max(column(end), column(start) + 1).min(columns));
+            let label = format!("{}", job);
+            let block = if last - first > label.len() { format!("|{}", label) } else { label };
+            for (i, cell) in line[first..last].iter_mut().enumerate() {
+                *cell = block.chars().nth(i).unwrap_or('-');
+            }
+        }
+
+        let busy: usize = row.iter().map(|x| x.2 - x.1).sum();
+        let idle = if makespan == 0 { 0.0 } else { (makespan - busy) as f64 / makespan as f64 * 100.0 };
+        writeln!(out, "{:>w$} {}| idle {:5.1}%", format!("M{}", machine), line.iter().collect::<String>(), idle,
+                 w = label_width).unwrap();
+    }
+    writeln!(out, "{:>w$} 0{:>c$}", "", makespan, w = label_width, c = columns).unwrap();
+    out
+}
+
+impl BlackBox {
+    pub fn gantt_ascii(&self, width: usize) -> String {
+        ascii(&CandidateSchedule { schedule: self.best_candidate.schedule.clone() }, width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::gantt::*;
+    use crate::jssp::results::load_schedule;
+
+    fn solved() -> BlackBox {
+        let mut process = test_process(6);
+        let candidate = <BlackBox as NullaryOperator>::apply(&mut process);
+        process.update(&candidate);
+        process.finalize()
+    }
+
+    #[test]
+    fn svg_draws_every_operation_up_to_the_makespan() {
+        let process = solved();
+        let schedule = CandidateSchedule { schedule: process.best_candidate.schedule.clone() };
+        let makespan = process.best_candidate.makespan;
+        let out = svg(&process.instance, &schedule, 800, false);
+
+        assert_well_formed(&out);
+        assert_eq!(out.matches("<title>job ").count(), process.instance.n * process.instance.m);
+        assert!(out.contains(&format!("(makespan {})", makespan)));
+        assert!(out.contains(&format!(r#"<line x1="{}" y1="{}" x2="{:.1}" y2="{}" stroke="black"/>"#,
+                                      MARGIN_LEFT, MARGIN_TOP + process.instance.m * LANE_HEIGHT,
+                                      (800 - MARGIN_RIGHT) as f64, MARGIN_TOP + process.instance.m * LANE_HEIGHT)));
+        assert!(!out.contains("crimson"));
+
+        let critical = svg(&process.instance, &schedule, 800, true);
+        assert_eq!(critical.matches(r#"stroke="crimson""#).count(), critical_operations(&process.instance, &schedule).len());
+    }
+
+    #[test]
+    fn ascii_scales_blocks_and_reports_idle_time() {
+        let schedule = CandidateSchedule { schedule: vec![vec![0, 0, 3, 1, 3, 5], vec![1, 0, 2, 0, 3, 6]] };
+        let out = ascii(&schedule, 76);
+        let lines = out.lines().collect_vec();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], format!("M0 |0{}|1{}| idle  16.7%", "-".repeat(28), "-".repeat(18) + &" ".repeat(10)));
+        assert_eq!(lines[1], format!("M1 |1{}{}|0{}| idle  16.7%", "-".repeat(18), " ".repeat(10), "-".repeat(28)));
+        assert_eq!(lines[2].trim_end().len(), lines[0].find("| idle").unwrap() + 1);
+        assert!(lines[2].ends_with(" 6"));
+    }
+
+    #[test]
+    fn ascii_keeps_short_operations_visible() {
+        let schedule = CandidateSchedule { schedule: vec![vec![12, 0, 1, 3, 10, 100]] };
+        let out = ascii(&schedule, 0);
+        assert!(out.starts_with("M0 1|3"));
+        assert!(out.contains("| idle   9.0%"));
+    }
+
+    #[test]
+    fn saved_solutions_load_back_into_the_same_schedule() {
+        let process = solved();
+        let path = std::env::temp_dir().join(format!("gantt-{}.json", std::process::id()));
+        process.save_to(&path).unwrap();
+        let (instance, schedule) = load_schedule(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(schedule.schedule, process.best_candidate.schedule);
+        assert_eq!(instance.jobs, process.instance.jobs);
+        assert_eq!(svg(&instance, &schedule, 800, true), svg(&process.instance, &schedule, 800, true));
+    }
+}
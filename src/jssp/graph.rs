@@ -0,0 +1,224 @@
+use crate::jssp::*;
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Block {
+    pub machine: usize,
+    pub first: usize,
+    pub length: usize,
+}
+
+#[derive(Clone)]
+pub struct DisjunctiveGraph {
+    n: usize,
+    m: usize,
+    machines: Vec<usize>,
+    durations: Vec<usize>,
+    sequences: Vec<Vec<usize>>,
+    positions: Vec<usize>,
+}
+
+impl DisjunctiveGraph {
+    pub fn new(instance: &Instance, schedule: &CandidateSchedule) -> Self {
+        Self::from_schedule(instance, &schedule.schedule)
+    }
+
+    pub fn from_candidate(instance: &Instance, candidate: &Candidate) -> Self {
+        Self::from_schedule(instance, &candidate.schedule)
+    }
+
+    fn from_schedule(instance: &Instance, schedule: &[Vec<usize>]) -> Self {
+        let (n, m) = (instance.n, instance.m);
+
+        let mut machines = vec![0; n * m];
+        let mut durations = vec![0; n * m];
+        let mut steps = vec![vec![0; m]; n];
+        for (job, operations) in instance.jobs.iter().enumerate() {
+            for step in 0..m {
+                machines[job * m + step] = operations[2 * step];
+                durations[job * m + step] = operations[2 * step + 1];
+                steps[job][operations[2 * step]] = step;
+            }
+        }
+
+        let mut positions = vec![0; n * m];
+        let sequences: Vec<Vec<usize>> = schedule.iter().enumerate().map(|(machine, x)| {
+            x.iter().step_by(3).enumerate().map(|(i, &job)| {
+                let node = job * m + steps[job][machine];
+                positions[node] = i;
+                node
+            }).collect()
+        }).collect();
+
+        Self { n, m, machines, durations, sequences, positions }
+    }
+
+    pub fn node_count(&self) -> usize { self.n * self.m }
+
+    pub fn job(&self, node: usize) -> usize { node / self.m }
+
+    pub fn machine(&self, node: usize) -> usize { self.machines[node] }
+
+    pub fn duration(&self, node: usize) -> usize { self.durations[node] }
+
+    pub fn sequences(&self) -> &Vec<Vec<usize>> { &self.sequences }
+
+    pub fn job_predecessor(&self, node: usize) -> Option<usize> {
+        if !node.is_multiple_of(self.m) { Some(node - 1) } else { None }
+    }
+
+    pub fn job_successor(&self, node: usize) -> Option<usize> {
+        if node % self.m < self.m - 1 { Some(node + 1) } else { None }
+    }
+
+    pub fn machine_predecessor(&self, node: usize) -> Option<usize> {
+        let position = self.positions[node];
+        if position > 0 { Some(self.sequences[self.machines[node]][position - 1]) } else { None }
+    }
+
+    pub fn machine_successor(&self, node: usize) -> Option<usize> {
+        self.sequences[self.machines[node]].get(self.positions[node] + 1).copied()
+    }
+
+    pub fn swap(&mut self, machine: usize, i: usize) {
+        self.sequences[machine].swap(i, i + 1);
+        self.positions[self.sequences[machine][i]] = i;
+        self.positions[self.sequences[machine][i + 1]] = i + 1;
+    }
+
+    pub fn shift(&mut self, machine: usize, from: usize, to: usize) {
+        let node = self.sequences[machine].remove(from);
+        self.sequences[machine].insert(to, node);
+        for i in min(from, to)..=max(from, to) { self.positions[self.sequences[machine][i]] = i; }
+    }
+
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut degree: Vec<usize> = (0..self.node_count()).map(|x|
+            self.job_predecessor(x).is_some() as usize + self.machine_predecessor(x).is_some() as usize
+        ).collect();
+
+        let mut queue: VecDeque<usize> = (0..self.node_count()).filter(|&x| degree[x] == 0).collect();
+        let mut order = Vec::with_capacity(self.node_count());
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in self.job_successor(node).into_iter().chain(self.machine_successor(node)) {
+                degree[next] -= 1;
+                if degree[next] == 0 { queue.push_back(next); }
+            }
+        }
+
+        if order.len() == self.node_count() { Some(order) } else { None }
+    }
+
+    pub fn order(&self) -> Option<Vec<usize>> {
+        self.topological_order().map(|x| x.into_iter().map(|node| self.job(node)).collect())
+    }
+
+    pub fn heads(&self) -> Vec<usize> {
+        let mut heads = vec![0; self.node_count()];
+        for node in self.topological_order().expect("Failed to orient the graph") {
+            heads[node] = self.job_predecessor(node).into_iter().chain(self.machine_predecessor(node))
+                .map(|x| heads[x] + self.durations[x]).max().unwrap_or(0);
+        }
+        heads
+    }
+
+    pub fn tails(&self) -> Vec<usize> {
+        let mut tails = vec![0; self.node_count()];
+        for node in self.topological_order().expect("Failed to orient the graph").into_iter().rev() {
+            tails[node] = self.job_successor(node).into_iter().chain(self.machine_successor(node))
+                .map(|x| tails[x] + self.durations[x]).max().unwrap_or(0);
+        }
+        tails
+    }
+
+    pub fn makespan(&self) -> usize {
+        let heads = self.heads();
+        (0..self.node_count()).map(|x| heads[x] + self.durations[x]).max().unwrap_or(0)
+    }
+
+    pub fn critical_path(&self) -> Vec<usize> {
+        let (heads, tails) = (self.heads(), self.tails());
+        let makespan = (0..self.node_count()).map(|x| heads[x] + self.durations[x]).max().unwrap_or(0);
+        let is_critical = |x: usize| heads[x] + self.durations[x] + tails[x] == makespan;
+
+        let mut node = match (0..self.node_count()).find(|&x| heads[x] == 0 && is_critical(x)) {
+            Some(node) => node,
+            None => return vec![],
+        };
+
+        let mut path = vec![node];
+        while let Some(next) = self.machine_successor(node).into_iter().chain(self.job_successor(node))
+            .find(|&x| heads[x] == heads[node] + self.durations[node] && is_critical(x)) {
+            path.push(next);
+            node = next;
+        }
+        path
+    }
+
+    pub fn critical_blocks(&self) -> Vec<Block> {
+        let mut blocks: Vec<Block> = Vec::new();
+        for node in self.critical_path() {
+            let machine = self.machines[node];
+            match blocks.last_mut() {
+                Some(block) if block.machine == machine => block.length += 1,
+                _ => blocks.push(Block { machine, first: self.positions[node], length: 1 }),
+            }
+        }
+        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::graph::{Block, DisjunctiveGraph};
+
+    /// Decoding jobs 0, 1, 2 in turn gives a single critical path of length 20 through every operation
+    /// except job 1's first one.
+    fn graph() -> DisjunctiveGraph {
+        let instance = Instance::load_reader("tiny", "3 3\n0 3 1 2 2 2\n0 2 2 1 1 4\n1 4 2 3 0 1\n".as_bytes()).unwrap();
+        DisjunctiveGraph::from_candidate(&instance, &decode_reference(&instance, &[0, 0, 0, 1, 1, 1, 2, 2, 2]))
+    }
+
+    #[test]
+    fn heads_and_tails() {
+        let graph = graph();
+        assert_eq!(graph.heads(), vec![0, 3, 5, 3, 7, 8, 12, 16, 19]);
+        assert_eq!(graph.tails(), vec![17, 15, 13, 13, 12, 8, 4, 1, 0]);
+        assert_eq!(graph.makespan(), 20);
+    }
+
+    #[test]
+    fn critical_path_and_blocks() {
+        let graph = graph();
+        assert_eq!(graph.critical_path(), vec![0, 1, 2, 4, 5, 6, 7, 8]);
+        assert_eq!(graph.critical_blocks(), vec![
+            Block { machine: 0, first: 0, length: 1 },
+            Block { machine: 1, first: 0, length: 1 },
+            Block { machine: 2, first: 0, length: 2 },
+            Block { machine: 1, first: 1, length: 2 },
+            Block { machine: 2, first: 2, length: 1 },
+            Block { machine: 0, first: 2, length: 1 },
+        ]);
+    }
+
+    #[test]
+    fn critical_path_spans_the_makespan() {
+        let mut process = test_process(9);
+
+        for _ in 0..20 {
+            let candidate = <BlackBox as NullaryOperator>::apply(&mut process);
+            let graph = process.graph(&candidate);
+            let (heads, tails) = (graph.heads(), graph.tails());
+            assert_eq!(graph.makespan(), candidate.makespan);
+            assert!((0..graph.node_count()).all(|x| heads[x] + graph.duration(x) + tails[x] <= candidate.makespan));
+
+            let path = graph.critical_path();
+            assert_eq!(heads[path[0]], 0);
+            assert_eq!(heads[*path.last().unwrap()] + graph.duration(*path.last().unwrap()), candidate.makespan);
+            assert!(path.windows(2).all(|x| heads[x[1]] == heads[x[0]] + graph.duration(x[0])));
+            assert_eq!(graph.critical_blocks().iter().map(|x| x.length).sum::<usize>(), path.len());
+        }
+    }
+}
@@ -1,37 +1,60 @@
 use crate::jssp::*;
+use crate::jssp::neighbourhood::Neighbourhood;
 use std::thread;
 
 
+#[derive(Clone, Copy)]
+enum Search {
+    Random(UnaryOperator),
+    Steepest(Neighbourhood),
+}
+
 pub struct HillClimber {
     process: BlackBox,
-    unary_op: String,
+    search: Search,
     reset_threshold: usize,
     reset_counter: usize,
 }
 
+/// `unary_op` names a registered unary operator, or a neighbourhood (`n1`, `n5`, `n6`) for steepest descent.
+fn search(unary_op: &str) -> Result<Search, OperatorError> {
+    Ok(match unary_op.parse::<Neighbourhood>() {
+        Ok(neighbourhood) => Search::Steepest(neighbourhood),
+        Err(_) => Search::Random(unary_operator(unary_op).map_err(|_| OperatorError::Unknown {
+            name: String::from(unary_op),
+            expected: unary_operator_names().into_iter().chain(vec!["n1", "n5", "n6"]).join(", "),
+        })?),
+    })
+}
+
+/// Checks `unary_op` the way `HillClimber::new` resolves it.
+pub fn check_operator(unary_op: &str) -> Result<(), OperatorError> {
+    search(unary_op).map(|_| ())
+}
+
 impl HillClimber {
-    pub fn new(instance: &Instance, reset_threshold: usize, unary_op: &str) -> Self {
-        Self {
-            process: BlackBox::new(instance.clone(), String::from("HillClimber with resets")),
-            unary_op: String::from(unary_op),
+    pub fn new(instance: Arc<Instance>, reset_threshold: usize, unary_op: &str, config: RunConfig) -> Result<Self, OperatorError> {
+        let search = search(unary_op)?;
+
+        Ok(Self {
+            process: BlackBox::new(instance, String::from("HillClimber with resets"), config),
+            search,
             reset_threshold,
             reset_counter: 0,
-        }
+        })
     }
 
     pub fn solve(&mut self) -> BlackBox {
+        let search_operator = match self.search {
+            Search::Random(operator) => operator,
+            Search::Steepest(neighbourhood) => return self.solve_steepest(neighbourhood),
+        };
+
         let mut best_candidate: Candidate = <BlackBox as NullaryOperator>::apply(&mut self.process);
         let mut next_candidate;
         let mut prev_candidate: Candidate = best_candidate.clone();
 
-        let search_operator: fn(&mut BlackBox, &Candidate) -> Candidate
-            = match self.unary_op.to_lowercase().as_str() {
-            "1swap" => <BlackBox as UnaryOperator1Swap>::apply,
-            "nswap" => <BlackBox as UnaryOperatorNSwap>::apply,
-            _ => panic!("Unsupported operator"),
-        };
-
-        while !(self.process.should_terminate)(&mut self.process) {
+        while !self.process.should_terminate() {
             if self.should_reset() {
                 prev_candidate = <BlackBox as NullaryOperator>::apply(&mut self.process);
                 self.reset_counter = 0;
@@ -40,7 +63,7 @@ impl HillClimber {
 
             if next_candidate > prev_candidate {
                 prev_candidate = next_candidate;
-            } else {}
+            }
 
             if prev_candidate > best_candidate {
                 best_candidate = prev_candidate.clone();
@@ -52,10 +75,46 @@ impl HillClimber {
         self.process.clone().finalize()
     }
 
+    /// Moves to the best neighbour while it improves and restarts from a random candidate at local optima.
+    pub fn solve_steepest(&mut self, neighbourhood: Neighbourhood) -> BlackBox {
+        let mut best_candidate: Candidate = <BlackBox as NullaryOperator>::apply(&mut self.process);
+        let mut curr_candidate: Candidate = best_candidate.clone();
+        self.process.update_history(&best_candidate);
+
+        let mut terminated = false;
+        while !terminated {
+            let neighbours = self.process.neighbours(&curr_candidate, neighbourhood);
+            terminated = self.process.config.termination.is_met(&self.process);
+
+            match neighbours.into_iter().map(|(_, x)| x).min_by_key(|x| x.makespan) {
+                Some(next_candidate) if next_candidate > curr_candidate => curr_candidate = next_candidate,
+                _ if !terminated => {
+                    curr_candidate = <BlackBox as NullaryOperator>::apply(&mut self.process);
+                    terminated = self.process.should_terminate();
+                }
+                _ => {}
+            }
+
+            if curr_candidate > best_candidate {
+                best_candidate = curr_candidate.clone();
+                self.process.update_history(&best_candidate);
+            }
+        }
+        self.process.update(&best_candidate);
+        self.process.clone().finalize()
+    }
+
     pub fn solve_threaded(&self) -> BlackBox {
-        let handles = (0..thread::available_concurrency().expect("Failed to get thread count").get())
-            .map(|_| {
-                let mut hc = Self::new(&self.process.instance, self.reset_threshold, self.unary_op.clone().as_str());
+        let thread_count = thread::available_parallelism().expect("Failed to get thread count").get();
+        let handles = derive_seeds(self.process.seed, thread_count).into_iter()
+            .map(|seed| {
+                let config = RunConfig { seed: Some(seed), ..self.process.config.clone() };
+                let mut hc = Self {
+                    process: BlackBox::new(self.process.instance.clone(), self.process.metaheurestic.clone(), config),
+                    search: self.search,
+                    reset_threshold: self.reset_threshold,
+                    reset_counter: 0,
+                };
                 thread::spawn(move || hc.solve())
             })
             .collect_vec();
@@ -67,8 +126,8 @@ impl HillClimber {
 
         println!("Used {} threads", bbs.len());
         println!("With {} total iterations", bbs.iter().fold(0, |a, b| a + b.termination_counter));
-        println!("In the time of {}s", self.process.instance.termination_limit);
-        bbs.into_iter().max_by_key(|x| x.best_candidate.makespan).unwrap().clone()
+        println!("Until {}", self.process.config.termination);
+        bbs.into_iter().min_by_key(|x| x.best_candidate.makespan).unwrap()
     }
 
     fn should_reset(&mut self) -> bool {
@@ -76,3 +135,32 @@ impl HillClimber {
         self.reset_counter >= self.reset_threshold
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::experiment::Algorithm;
+    use crate::jssp::hc::HillClimber;
+    use crate::jssp::rs::RandomSample;
+
+    #[test]
+    fn operator_errors_list_neighbourhoods() {
+        let error = HillClimber::new(test_instance(), 10, "bogus", RunConfig::new(Termination::evaluations(0)).with_seed(3)).err().unwrap();
+        assert!(error.to_string().ends_with("insertion, inversion, scramble, n1, n5, n6"), "{}", error);
+        assert_eq!("hc:operator=bogus".parse::<Algorithm>().unwrap_err(), error.to_string());
+        assert!("hc:operator=n6".parse::<Algorithm>().is_ok());
+    }
+
+    #[test]
+    fn seeded_threaded_runs_repeat() {
+        let instance = test_instance();
+        let config = |seed| RunConfig::new(Termination::evaluations(300)).with_seed(seed);
+        let hc = |seed| HillClimber::new(instance.clone(), 50, "nswap", config(seed)).unwrap().solve_threaded();
+        let rs = |seed| RandomSample::new(instance.clone(), config(seed)).solve_threaded();
+
+        assert_eq!(outcome(&hc(5)), outcome(&hc(5)));
+        assert_ne!(outcome(&hc(5)).0, outcome(&hc(6)).0);
+        assert_eq!(outcome(&rs(5)), outcome(&rs(5)));
+        assert_ne!(outcome(&rs(5)).0, outcome(&rs(6)).0);
+    }
+}
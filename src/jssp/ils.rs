@@ -0,0 +1,192 @@
+use crate::jssp::*;
+use crate::jssp::neighbourhood::Neighbourhood;
+use std::str::FromStr;
+
+/// Which local optimum the next perturbation starts from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Acceptance {
+    /// Only a strictly better local optimum replaces the current one.
+    Better,
+    /// A local optimum at least as good as the current one replaces it.
+    BetterOrEqual,
+    /// Every new local optimum replaces the current one.
+    RandomWalk,
+    /// As `Better`, but restarts from a random candidate after the given number of kicks without improvement.
+    Restart(usize),
+}
+
+impl Acceptance {
+    fn accepts(&self, next: &Candidate, current: &Candidate) -> bool {
+        match self {
+            Acceptance::Better | Acceptance::Restart(_) => next.makespan < current.makespan,
+            Acceptance::BetterOrEqual => next.makespan <= current.makespan,
+            Acceptance::RandomWalk => true,
+        }
+    }
+}
+
+impl Display for Acceptance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Acceptance::Better => write!(f, "better"),
+            Acceptance::BetterOrEqual => write!(f, "better-or-equal"),
+            Acceptance::RandomWalk => write!(f, "random-walk"),
+            Acceptance::Restart(patience) => write!(f, "restart{}", patience),
+        }
+    }
+}
+
+impl FromStr for Acceptance {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        match s.as_str() {
+            "better" => Ok(Acceptance::Better),
+            "better-or-equal" => Ok(Acceptance::BetterOrEqual),
+            "random-walk" => Ok(Acceptance::RandomWalk),
+            "restart" => Ok(Acceptance::Restart(100)),
+            _ => match s.strip_prefix("restart").map(|x| x.parse::<usize>()) {
+                Some(Ok(patience)) if patience > 0 => Ok(Acceptance::Restart(patience)),
+                _ => Err(format!("Unsupported acceptance {}, expected one of better, better-or-equal, random-walk, restart<kicks>", s)),
+            },
+        }
+    }
+}
+
+pub struct IteratedLocalSearch {
+    process: BlackBox,
+    neighbourhood: Neighbourhood,
+    strength: usize,
+    acceptance: Acceptance,
+}
+
+impl IteratedLocalSearch {
+    /// Local search is steepest descent in `neighbourhood`; a kick applies `strength` random swaps.
+    pub fn new(instance: Arc<Instance>, neighbourhood: Neighbourhood, strength: usize, acceptance: Acceptance, config: RunConfig) -> Self {
+        Self {
+            process: BlackBox::new(instance, String::from("Iterated Local Search"), config),
+            neighbourhood,
+            strength: max(strength, 1),
+            acceptance,
+        }
+    }
+
+    pub fn solve(&mut self) -> BlackBox {
+        let mut best_candidate: Candidate = <BlackBox as NullaryOperator>::apply(&mut self.process);
+        self.process.update_history(&best_candidate);
+        let mut curr_candidate = self.descend(best_candidate.clone(), &mut best_candidate);
+
+        let mut failures = 0;
+        while !self.process.should_terminate() {
+            if let Acceptance::Restart(patience) = self.acceptance {
+                if failures >= patience {
+                    let restarted = <BlackBox as NullaryOperator>::apply(&mut self.process);
+                    curr_candidate = self.descend(restarted, &mut best_candidate);
+                    failures = 0;
+                    continue;
+                }
+            }
+
+            let kicked = self.kick(&curr_candidate);
+            let next_candidate = self.descend(kicked, &mut best_candidate);
+
+            failures = if next_candidate > curr_candidate { 0 } else { failures + 1 };
+            if self.acceptance.accepts(&next_candidate, &curr_candidate) { curr_candidate = next_candidate; }
+        }
+
+        self.process.update(&best_candidate);
+        self.process.clone().finalize()
+    }
+
+    /// Moves to the best neighbour while it improves, recording new best candidates on the way.
+    fn descend(&mut self, mut candidate: Candidate, best_candidate: &mut Candidate) -> Candidate {
+        loop {
+            if candidate > *best_candidate {
+                *best_candidate = candidate.clone();
+                self.process.update_history(best_candidate);
+            }
+
+            if self.process.config.termination.is_met(&self.process) { return candidate; }
+
+            let neighbours = self.process.neighbours(&candidate, self.neighbourhood);
+            match neighbours.into_iter().map(|(_, x)| x).min_by_key(|x| x.makespan) {
+                Some(next_candidate) if next_candidate > candidate => candidate = next_candidate,
+                _ => return candidate,
+            }
+        }
+    }
+
+    fn kick(&mut self, based: &Candidate) -> Candidate {
+        let mut result = based.order.clone();
+        let high = based.order.len();
+        let mut first = high;
+
+        for _ in 0..self.strength {
+            let (i, mut j) = (self.process.random.gen_range(0..high), self.process.random.gen_range(0..high));
+            while result[i] == result[j] { j = self.process.random.gen_range(0..high) }
+            result.swap(i, j);
+            first = min(first, min(i, j));
+        }
+        self.process.neighbour(based, result, first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::ils::{Acceptance, IteratedLocalSearch};
+    use crate::jssp::neighbourhood::Neighbourhood;
+
+    fn candidate(makespan: usize) -> Candidate {
+        Candidate { makespan, order: vec![], schedule: vec![] }
+    }
+
+    #[test]
+    fn acceptance_rules() {
+        let (better, equal, worse, current) = (candidate(9), candidate(10), candidate(11), candidate(10));
+        for acceptance in [Acceptance::Better, Acceptance::Restart(5)].iter() {
+            assert!(acceptance.accepts(&better, &current));
+            assert!(!acceptance.accepts(&equal, &current));
+            assert!(!acceptance.accepts(&worse, &current));
+        }
+        assert!(Acceptance::BetterOrEqual.accepts(&better, &current));
+        assert!(Acceptance::BetterOrEqual.accepts(&equal, &current));
+        assert!(!Acceptance::BetterOrEqual.accepts(&worse, &current));
+        assert!(Acceptance::RandomWalk.accepts(&worse, &current));
+    }
+
+    #[test]
+    fn parses_acceptances() {
+        for acceptance in [Acceptance::Better, Acceptance::BetterOrEqual, Acceptance::RandomWalk, Acceptance::Restart(7)].iter() {
+            assert_eq!(acceptance.to_string().parse::<Acceptance>().as_ref(), Ok(acceptance));
+        }
+        assert_eq!("restart".parse::<Acceptance>(), Ok(Acceptance::Restart(100)));
+        assert!("restart0".parse::<Acceptance>().is_err());
+        assert!("worse".parse::<Acceptance>().is_err());
+    }
+
+    #[test]
+    fn kick_keeps_job_counts() {
+        let config = RunConfig::new(Termination::evaluations(0)).with_seed(6);
+        let mut ils = IteratedLocalSearch::new(test_instance(), Neighbourhood::N1, 4, Acceptance::Better, config);
+        let based = <BlackBox as NullaryOperator>::apply(&mut ils.process);
+        let jobs = based.order.iter().copied().sorted().collect_vec();
+        for _ in 0..100 {
+            let kicked = ils.kick(&based);
+            assert_ne!(kicked.order, based.order);
+            assert_eq!(kicked.order.iter().copied().sorted().collect_vec(), jobs);
+        }
+    }
+
+    #[test]
+    fn solves_with_every_acceptance() {
+        let instance = test_instance();
+        for &acceptance in [Acceptance::Better, Acceptance::BetterOrEqual, Acceptance::RandomWalk, Acceptance::Restart(1)].iter() {
+            let config = RunConfig::new(Termination::evaluations(3_000)).with_seed(8);
+            let result = IteratedLocalSearch::new(instance.clone(), Neighbourhood::N5, 3, acceptance, config).solve();
+            assert_eq!(instance.validate_candidate(&result.best_candidate), vec![], "{}", acceptance);
+            assert_eq!(result.best_candidate.makespan, result.best_makespan);
+        }
+    }
+}
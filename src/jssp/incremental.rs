@@ -0,0 +1,119 @@
+use crate::jssp::*;
+
+/// Semi-active decoder states of one base order, saved every `stride` positions, so that a
+/// neighbour sharing a prefix with the base is only simulated from the checkpoint before the
+/// first changed position. Checkpoints are recorded lazily as neighbours need them.
+#[derive(Clone)]
+pub(crate) struct PrefixCache {
+    order: Vec<usize>,
+    stride: usize,
+    m: usize,
+    n: usize,
+    states: Vec<usize>,
+    state: Vec<usize>,
+}
+
+impl PrefixCache {
+    pub(crate) fn new(instance: &Instance) -> Self {
+        let length = instance.n * instance.m;
+        Self {
+            order: Vec::with_capacity(length),
+            stride: max((length as f64).sqrt() as usize, 1),
+            m: instance.m,
+            n: instance.n,
+            states: Vec::new(),
+            state: vec![0; 2 * instance.m + 2 * instance.n],
+        }
+    }
+
+    fn width(&self) -> usize { 2 * self.m + 2 * self.n }
+
+    /// State layout is `[machine_state; m][machine_time; m][job_state; n][job_time; n]`.
+    fn step(jobs: &[Vec<usize>], state: &mut [usize], m: usize, job: usize) -> (usize, usize, usize, usize) {
+        let (machine_state, rest) = state.split_at_mut(m);
+        let (machine_time, rest) = rest.split_at_mut(m);
+        let n = rest.len() / 2;
+        let (job_state, job_time) = rest.split_at_mut(n);
+
+        let job_step = job_state[job] * 2;
+        let machine = jobs[job][job_step];
+        job_state[job] += 1;
+
+        let start = max(machine_time[machine], job_time[job]);
+        let end = start + jobs[job][job_step + 1];
+        machine_time[machine] = end;
+        job_time[job] = end;
+
+        let slot = machine_state[machine];
+        machine_state[machine] += 3;
+        (machine, slot, start, end)
+    }
+
+    /// Without a schedule in `based`, or with `lazy`, only the makespan is computed.
+    pub(crate) fn evaluate(&mut self, instance: &Instance, based: &Candidate, order: Vec<usize>, first: usize, lazy: bool) -> Candidate {
+        let width = self.width();
+        if self.order != based.order {
+            self.order.clone_from(&based.order);
+            self.states.clear();
+        }
+        if self.states.is_empty() { self.states.resize(width, 0); }
+
+        let checkpoint = first / self.stride;
+        let state = &mut self.state;
+        state.copy_from_slice(&self.states[self.states.len() - width..]);
+        while self.states.len() / width <= checkpoint {
+            let position = (self.states.len() / width - 1) * self.stride;
+            for &job in self.order[position..min(position + self.stride, self.order.len())].iter() {
+                Self::step(&instance.jobs, state, self.m, job);
+            }
+            self.states.extend_from_slice(state);
+        }
+
+        state.copy_from_slice(&self.states[checkpoint * width..(checkpoint + 1) * width]);
+        let mut schedule = if lazy || based.schedule.is_empty() { vec![] } else { based.schedule.clone() };
+        for &job in order[checkpoint * self.stride..].iter() {
+            let (machine, slot, start, end) = Self::step(&instance.jobs, state, self.m, job);
+            if let Some(row) = schedule.get_mut(machine) {
+                row[slot] = job;
+                row[slot + 1] = start;
+                row[slot + 2] = end;
+            }
+        }
+
+        let makespan = *state[self.m..2 * self.m].iter().max().expect("Failed to find makespan.");
+        Candidate { makespan, order, schedule }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+
+    fn check(lazy: bool) {
+        let instance = Arc::new(Instance::generate("tai", 20, 10, 1_274_926_328, 2_017_376_483));
+        let config = RunConfig::new(Termination::evaluations(0)).with_seed(7).with_lazy_schedule(lazy);
+        let mut process = BlackBox::new(instance.clone(), String::from("test"), config);
+
+        for name in unary_operator_names() {
+            let operator = unary_operator(name).unwrap();
+            let mut based = <BlackBox as NullaryOperator>::apply(&mut process);
+            for i in 0..500 {
+                let next = operator(&mut process, &based);
+                let mut full = CandidateSchedule::new(instance.m, instance.n);
+                let makespan = (process.mapping)(&mut process, &next.order, Some(&mut full));
+                assert_eq!(next.makespan, makespan, "{} passes a late first position", name);
+                assert_eq!(next.makespan, process.find_makespan(&full));
+                assert_eq!(full.schedule, decode_reference(&instance, &next.order).schedule);
+                if lazy { assert!(next.schedule.is_empty()); } else { assert_eq!(next.schedule, full.schedule, "{}", name); }
+
+                if i % 7 == 0 { based = next; }
+            }
+        }
+    }
+
+    #[test]
+    fn matches_full_decoding() { check(false); }
+
+    #[test]
+    fn matches_full_decoding_lazily() { check(true); }
+}
@@ -0,0 +1,188 @@
+use crate::jssp::*;
+use crate::jssp::graph::DisjunctiveGraph;
+use std::str::FromStr;
+
+/// A change to one machine sequence, given as positions in that sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Move {
+    /// Exchange the operations at `position` and `position + 1`.
+    Swap { machine: usize, position: usize },
+    /// Take the operation at `from` out and reinsert it at `to`.
+    Shift { machine: usize, from: usize, to: usize },
+}
+
+impl Move {
+    pub fn machine(&self) -> usize {
+        match *self {
+            Move::Swap { machine, .. } | Move::Shift { machine, .. } => machine,
+        }
+    }
+
+    pub fn apply(&self, graph: &mut DisjunctiveGraph) {
+        match *self {
+            Move::Swap { machine, position } => graph.swap(machine, position),
+            Move::Shift { machine, from, to } => graph.shift(machine, from, to),
+        }
+    }
+}
+
+/// Neighbourhoods built on the critical blocks of a schedule's disjunctive graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Swap any two adjacent critical operations of a block (van Laarhoven et al.).
+    N1,
+    /// Swap the first two and last two operations of each block, except at the ends of the path (Nowicki-Smutnicki).
+    N5,
+    /// Move an operation of a block to the front or the back of the block (Balas-Vazacopoulos).
+    N6,
+}
+
+impl Neighbourhood {
+    fn candidates(&self, graph: &DisjunctiveGraph) -> Vec<Move> {
+        let blocks = graph.critical_blocks();
+        let last = blocks.len().saturating_sub(1);
+
+        let mut moves = Vec::new();
+        for (i, block) in blocks.iter().enumerate().filter(|(_, x)| x.length >= 2) {
+            let (machine, first, end) = (block.machine, block.first, block.first + block.length - 1);
+            match self {
+                Neighbourhood::N1 => moves.extend((first..end).map(|position| Move::Swap { machine, position })),
+                Neighbourhood::N5 => {
+                    if i > 0 { moves.push(Move::Swap { machine, position: first }); }
+                    if i < last && (i == 0 || block.length > 2) { moves.push(Move::Swap { machine, position: end - 1 }); }
+                }
+                Neighbourhood::N6 => {
+                    moves.extend((first + 1..=end).map(|from| Move::Shift { machine, from, to: first }));
+                    if block.length > 2 { moves.extend((first..end).map(|from| Move::Shift { machine, from, to: end })); }
+                }
+            }
+        }
+        moves
+    }
+
+    fn is_feasible(&self, graph: &DisjunctiveGraph, mv: Move) -> bool {
+        match self {
+            Neighbourhood::N1 | Neighbourhood::N5 => true,
+            Neighbourhood::N6 => {
+                let mut graph = graph.clone();
+                mv.apply(&mut graph);
+                graph.topological_order().is_some()
+            }
+        }
+    }
+
+    /// Every move of the neighbourhood that keeps the graph acyclic.
+    pub fn moves(&self, graph: &DisjunctiveGraph) -> Vec<Move> {
+        self.candidates(graph).into_iter().filter(|&x| self.is_feasible(graph, x)).collect()
+    }
+
+    /// One feasible move drawn uniformly, checking feasibility only until one is found.
+    pub fn sample<R: Rng>(&self, graph: &DisjunctiveGraph, random: &mut R) -> Option<Move> {
+        let mut moves = self.candidates(graph);
+        moves.shuffle(random);
+        moves.into_iter().find(|&x| self.is_feasible(graph, x))
+    }
+}
+
+impl Display for Neighbourhood {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Neighbourhood::N1 => write!(f, "n1"),
+            Neighbourhood::N5 => write!(f, "n5"),
+            Neighbourhood::N6 => write!(f, "n6"),
+        }
+    }
+}
+
+impl FromStr for Neighbourhood {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "n1" => Ok(Neighbourhood::N1),
+            "n5" => Ok(Neighbourhood::N5),
+            "n6" => Ok(Neighbourhood::N6),
+            _ => Err(format!("Unsupported neighbourhood {}", s)),
+        }
+    }
+}
+
+impl BlackBox {
+    pub(crate) fn graph(&mut self, candidate: &Candidate) -> DisjunctiveGraph {
+        if candidate.schedule.is_empty() {
+            let mut candidate = candidate.clone();
+            candidate.materialize(self);
+            DisjunctiveGraph::from_candidate(&self.instance, &candidate)
+        } else {
+            DisjunctiveGraph::from_candidate(&self.instance, candidate)
+        }
+    }
+
+    /// The neighbour is decoded semi-actively: the active decoder would reorder operations and lose the move.
+    pub(crate) fn apply_move(&mut self, graph: &DisjunctiveGraph, mv: Move) -> Candidate {
+        let mut graph = graph.clone();
+        mv.apply(&mut graph);
+        let order = graph.order().expect("Failed to order the neighbour");
+        Candidate::semi_active(order, self)
+    }
+
+    /// Evaluates every move of the neighbourhood; stops early once the termination criteria are met.
+    /// Iterations are left to the calling search loop, which counts one per neighbourhood.
+    pub(crate) fn neighbours(&mut self, candidate: &Candidate, neighbourhood: Neighbourhood) -> Vec<(Move, Candidate)> {
+        let graph = self.graph(candidate);
+        let mut neighbours = Vec::new();
+        for mv in neighbourhood.moves(&graph) {
+            if self.config.termination.is_met(self) { break; }
+            neighbours.push((mv, self.apply_move(&graph, mv)));
+        }
+        neighbours
+    }
+
+    pub(crate) fn sample_neighbour(&mut self, candidate: &Candidate, neighbourhood: Neighbourhood) -> Option<(Move, Candidate)> {
+        let graph = self.graph(candidate);
+        let mv = neighbourhood.sample(&graph, &mut self.random)?;
+        Some((mv, self.apply_move(&graph, mv)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::neighbourhood::Neighbourhood;
+
+    #[test]
+    fn moves_keep_the_graph_acyclic() {
+        let mut process = test_process(3);
+
+        for _ in 0..50 {
+            let candidate = <BlackBox as NullaryOperator>::apply(&mut process);
+            let graph = process.graph(&candidate);
+            for neighbourhood in [Neighbourhood::N1, Neighbourhood::N5, Neighbourhood::N6].iter() {
+                for mv in neighbourhood.moves(&graph) {
+                    let mut next = graph.clone();
+                    mv.apply(&mut next);
+                    assert!(next.topological_order().is_some(), "{} move {:?} creates a cycle", neighbourhood, mv);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn moves_keep_their_makespan_under_the_active_decoder() {
+        let instance = test_instance();
+        let config = RunConfig::new(Termination::evaluations(0)).with_seed(4).with_decoder(Decoder::Active);
+        let mut process = BlackBox::new(instance.clone(), String::from("test"), config);
+
+        for _ in 0..10 {
+            let candidate = <BlackBox as NullaryOperator>::apply(&mut process);
+            let graph = process.graph(&candidate);
+            for mv in Neighbourhood::N1.moves(&graph) {
+                let mut next = graph.clone();
+                mv.apply(&mut next);
+                let neighbour = process.apply_move(&graph, mv);
+                assert_eq!(neighbour.makespan, next.makespan(), "move {:?}", mv);
+                assert_eq!(instance.validate_candidate(&neighbour), vec![]);
+            }
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use crate::jssp::*;
+use crate::jssp::results::{RunResult, csv};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct GroupStatistics {
+    pub instance: String,
+    pub algorithm: String,
+    pub runs: usize,
+    pub min: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub std: f64,
+    pub max: usize,
+    pub lower_bound: usize,
+    pub best_gap: f64,
+    pub mean_gap: f64,
+    pub evaluations_per_second: f64,
+    pub success_rate: f64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Comparison {
+    pub instance: String,
+    pub first: String,
+    pub second: String,
+    pub u: f64,
+    pub z: f64,
+    pub p_value: f64,
+}
+
+impl Comparison {
+    pub fn better(&self, alpha: f64) -> Option<&str> {
+        if self.p_value >= alpha { None } else if self.z < 0.0 { Some(&self.first) } else { Some(&self.second) }
+    }
+}
+
+pub struct Report {
+    pub groups: Vec<GroupStatistics>,
+    pub comparisons: Vec<Comparison>,
+    pub target_gap: f64,
+    pub alpha: f64,
+}
+
+impl Report {
+    /// A run succeeds when its makespan is within `target_gap` of the best known makespan,
+    /// or of the lower bound when none is known.
+    pub fn new(results: &[RunResult], target_gap: f64, alpha: f64) -> Self {
+        let mut grouped: BTreeMap<(String, String), Vec<&RunResult>> = BTreeMap::new();
+        for result in results {
+            grouped.entry((result.instance.clone(), result.algorithm.clone())).or_default().push(result);
+        }
+
+        let groups = grouped.iter().map(|((instance, algorithm), runs)| {
+            let makespans = runs.iter().map(|x| x.makespan as f64).collect_vec();
+            let lower_bound = runs.iter().map(|x| x.lower_bound).max().unwrap();
+            let target = runs[0].best_known.unwrap_or(lower_bound) as f64 * (1.0 + target_gap);
+            let gap = |makespan: f64| (makespan - lower_bound as f64) / lower_bound as f64;
+            let seconds = runs.iter().map(|x| x.seconds).sum::<f64>();
+
+            GroupStatistics {
+                instance: instance.clone(),
+                algorithm: algorithm.clone(),
+                runs: runs.len(),
+                min: runs.iter().map(|x| x.makespan).min().unwrap(),
+                mean: mean(&makespans),
+                median: median(&makespans),
+                std: std(&makespans),
+                max: runs.iter().map(|x| x.makespan).max().unwrap(),
+                lower_bound,
+                best_gap: gap(makespans.iter().cloned().fold(f64::INFINITY, f64::min)),
+                mean_gap: gap(mean(&makespans)),
+                evaluations_per_second: if seconds > 0.0 { runs.iter().map(|x| x.evaluations).sum::<usize>() as f64 / seconds } else { 0.0 },
+                success_rate: makespans.iter().filter(|&&x| x <= target).count() as f64 / runs.len() as f64,
+            }
+        }).collect_vec();
+
+        let mut comparisons = Vec::new();
+        for (instance, group) in &grouped.iter().chunk_by(|((instance, _), _)| instance.clone()) {
+            for [((_, first), a), ((_, second), b)] in group.collect_vec().into_iter().array_combinations() {
+                let a = a.iter().map(|x| x.makespan as f64).collect_vec();
+                let b = b.iter().map(|x| x.makespan as f64).collect_vec();
+                let (u, z, p_value) = mann_whitney(&a, &b);
+                comparisons.push(Comparison { instance: instance.clone(), first: first.clone(), second: second.clone(), u, z, p_value });
+            }
+        }
+
+        Self { groups, comparisons, target_gap, alpha }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        out += &format!("## Summary (success: within {:.1}% of the best known makespan)\n\n", self.target_gap * 100.0);
+        out += "| instance | algorithm | runs | min | mean | median | std | max | LB | best gap | mean gap | evals/s | success |\n";
+        out += "|---|---|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|\n";
+        for x in self.groups.iter() {
+            out += &format!("| {} | {} | {} | {} | {:.1} | {:.1} | {:.1} | {} | {} | {:.2}% | {:.2}% | {:.0} | {:.0}% |\n",
+                            x.instance, x.algorithm, x.runs, x.min, x.mean, x.median, x.std, x.max, x.lower_bound,
+                            x.best_gap * 100.0, x.mean_gap * 100.0, x.evaluations_per_second, x.success_rate * 100.0);
+        }
+
+        if !self.comparisons.is_empty() {
+            out += &format!("\n## Mann-Whitney U tests (alpha = {})\n\n", self.alpha);
+            out += "| instance | A | B | U | z | p | better |\n";
+            out += "|---|---|---|---:|---:|---:|---|\n";
+            for x in self.comparisons.iter() {
+                out += &format!("| {} | {} | {} | {:.1} | {:.3} | {:.4} | {} |\n", x.instance, x.first, x.second,
+                                x.u, x.z, x.p_value, x.better(self.alpha).unwrap_or("-"));
+            }
+        }
+        out
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("instance,algorithm,runs,min,mean,median,std,max,lower_bound,best_gap,mean_gap,evaluations_per_second,success_rate\n");
+        for x in self.groups.iter() {
+            out += &format!("{},{},{},{},{},{},{},{},{},{},{},{},{}\n", x.instance, csv(&x.algorithm), x.runs, x.min,
+                            x.mean, x.median, x.std, x.max, x.lower_bound, x.best_gap, x.mean_gap,
+                            x.evaluations_per_second, x.success_rate);
+        }
+        out
+    }
+
+    pub fn comparisons_to_csv(&self) -> String {
+        let mut out = String::from("instance,first,second,u,z,p_value,better\n");
+        for x in self.comparisons.iter() {
+            out += &format!("{},{},{},{},{},{},{}\n", x.instance, csv(&x.first), csv(&x.second), x.u, x.z, x.p_value,
+                            csv(x.better(self.alpha).unwrap_or("")));
+        }
+        out
+    }
+}
+
+pub fn mean(xs: &[f64]) -> f64 {
+    xs.iter().sum::<f64>() / xs.len() as f64
+}
+
+pub fn median(xs: &[f64]) -> f64 {
+    let mut xs = xs.to_vec();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let half = xs.len() / 2;
+    if xs.len().is_multiple_of(2) { (xs[half - 1] + xs[half]) / 2.0 } else { xs[half] }
+}
+
+/// Sample standard deviation, zero for a single run.
+pub fn std(xs: &[f64]) -> f64 {
+    if xs.len() < 2 { return 0.0; }
+    let mean = mean(xs);
+    (xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (xs.len() - 1) as f64).sqrt()
+}
+
+/// Two-sided Mann-Whitney U (Wilcoxon rank-sum) test using the tie-corrected normal approximation.
+/// Returns `(U, z, p)` for the first sample; a negative `z` means the first sample tends to be smaller.
+pub fn mann_whitney(a: &[f64], b: &[f64]) -> (f64, f64, f64) {
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let n = n1 + n2;
+    let mut all = a.iter().map(|&x| (x, true)).chain(b.iter().map(|&x| (x, false))).collect_vec();
+    all.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+
+    let mut rank_sum = 0.0;
+    let mut ties = 0.0;
+    let mut i = 0;
+    while i < all.len() {
+        let j = (i..all.len()).find(|&j| all[j].0 != all[i].0).unwrap_or(all.len());
+        let rank = (i + j + 1) as f64 / 2.0;
+        rank_sum += all[i..j].iter().filter(|x| x.1).count() as f64 * rank;
+        let t = (j - i) as f64;
+        ties += t * t * t - t;
+        i = j;
+    }
+
+    let u = rank_sum - n1 * (n1 + 1.0) / 2.0;
+    let mu = n1 * n2 / 2.0;
+    let sigma = (n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)))).sqrt();
+    if sigma == 0.0 || !sigma.is_finite() { return (u, 0.0, 1.0); }
+
+    let z = ((u - mu).abs() - 0.5).max(0.0) * (u - mu).signum() / sigma;
+    (u, z, erfc(z.abs() / std::f64::consts::SQRT_2).min(1.0))
+}
+
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let y = t * (-x * x - 1.26551223 + t * (1.00002368 + t * (0.37409196 + t * (0.09678418 + t * (-0.18628806
+        + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))))).exp();
+    if x >= 0.0 { y } else { 2.0 - y }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::report::*;
+
+    fn close(a: f64, b: f64) -> bool { (a - b).abs() < 1e-6 }
+
+    #[test]
+    fn summary_statistics() {
+        let xs = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert!(close(mean(&xs), 5.0));
+        assert!(close(median(&xs), 4.5));
+        assert!(close(median(&xs[..7]), 4.0));
+        assert!(close(std(&xs), 2.138089935299395));
+        assert!(close(std(&[3.0]), 0.0));
+    }
+
+    #[test]
+    fn mann_whitney_without_ties() {
+        // The asymptotic example from the scipy.stats.mannwhitneyu documentation.
+        let (u, z, p) = mann_whitney(&[19.0, 22.0, 16.0, 29.0, 24.0], &[20.0, 11.0, 17.0, 12.0]);
+        assert!(close(u, 17.0));
+        assert!(close(z, 1.5921683328090657));
+        assert!(close(p, 0.11134688653314041));
+    }
+
+    #[test]
+    fn mann_whitney_with_ties() {
+        let (u, z, p) = mann_whitney(&[1.0, 2.0, 2.0, 3.0, 4.0], &[2.0, 3.0, 3.0, 5.0, 6.0, 6.0]);
+        assert!(close(u, 6.0));
+        assert!(close(z, -1.5846319609981194));
+        assert!(close(p, 0.11304997885632297));
+    }
+
+    #[test]
+    fn mann_whitney_of_identical_samples() {
+        assert_eq!(mann_whitney(&[5.0, 5.0], &[5.0, 5.0, 5.0]), (3.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn zero_seconds_give_no_rate() {
+        let result = RunResult {
+            instance: String::from("la01"), algorithm: String::from("rs"), makespan: 700, lower_bound: 666,
+            best_known: Some(666), iterations: 10, evaluations: 10, seconds: 0.0, history: vec![],
+        };
+        let report = Report::new(&[result], 0.0, 0.05);
+        assert_eq!(report.groups[0].evaluations_per_second, 0.0);
+        assert!(!report.to_csv().contains("inf") && !report.to_csv().contains("NaN"));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::jssp::*;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+custom_error! {pub ResultsError
+    Io{source: std::io::Error} = "Failed to read the results: {source}",
+    Json{source: serde_json::Error} = "Failed to parse the results: {source}",
+}
+
+#[derive(Deserialize)]
+struct SavedRun {
+    info: SavedInfo,
+    solution: SavedSolution,
+    history: Vec<(f64, usize)>,
+}
+
+#[derive(Deserialize)]
+struct SavedInfo {
+    instance: SavedInstance,
+    lower_bound: usize,
+    metaheurestic: String,
+    timetaken: String,
+    iteration_count: usize,
+    #[serde(default)]
+    evaluations: Option<usize>,
+    #[serde(default)]
+    seconds: Option<f64>,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SavedInstance {
+    name: String,
+    #[serde(default)]
+    best_known: Option<usize>,
+    #[serde(default)]
+    known_lower_bound: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct SavedSolution { makespan: usize }
+
+#[derive(Deserialize)]
+struct SavedSchedule {
+    info: SavedScheduleInfo,
+    solution: SavedScheduleSolution,
+}
+
+#[derive(Deserialize)]
+struct SavedScheduleInfo { instance: SavedInstanceData }
+
+#[derive(Deserialize)]
+struct SavedInstanceData {
+    name: String,
+    #[serde(rename = "type")]
+    type_: String,
+    machine_count: usize,
+    job_count: usize,
+    #[serde(default)]
+    seeds: Option<(usize, usize)>,
+    #[serde(default)]
+    best_known: Option<usize>,
+    #[serde(default)]
+    known_lower_bound: Option<usize>,
+    data: Vec<Vec<usize>>,
+}
+
+#[derive(Deserialize)]
+struct SavedScheduleSolution { schedule: Vec<Vec<usize>> }
+
+/// Rebuilds the instance embedded in a saved solution together with its schedule.
+pub fn load_schedule<P: AsRef<Path>>(path: P) -> Result<(Instance, CandidateSchedule), ResultsError> {
+    let saved: SavedSchedule = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let data = saved.info.instance;
+    let instance = Instance {
+        jobs: data.data,
+        name: data.name,
+        type_: if data.type_.eq_ignore_ascii_case("taillard") { InstanceType::TAILLARD } else { InstanceType::ORLIB },
+        m: data.machine_count,
+        n: data.job_count,
+        seeds: data.seeds,
+        best_known: data.best_known,
+        known_lower_bound: data.known_lower_bound,
+    };
+    Ok((instance, CandidateSchedule { schedule: saved.solution.schedule }))
+}
+
+#[derive(Clone, Debug)]
+pub struct RunResult {
+    pub instance: String,
+    pub algorithm: String,
+    pub makespan: usize,
+    pub lower_bound: usize,
+    pub best_known: Option<usize>,
+    pub iterations: usize,
+    pub evaluations: usize,
+    pub seconds: f64,
+    pub history: Vec<(f64, usize)>,
+}
+
+impl RunResult {
+    pub fn from_json(json: &str) -> Result<Self, ResultsError> {
+        let saved: SavedRun = serde_json::from_str(json)?;
+        let seconds = saved.info.seconds.unwrap_or_else(|| parse_timetaken(&saved.info.timetaken));
+
+        Ok(Self {
+            instance: saved.info.instance.name,
+            algorithm: saved.info.label.unwrap_or(saved.info.metaheurestic),
+            makespan: saved.solution.makespan,
+            lower_bound: max(saved.info.lower_bound, saved.info.instance.known_lower_bound.unwrap_or(0)),
+            best_known: saved.info.instance.best_known,
+            iterations: saved.info.iteration_count,
+            evaluations: saved.info.evaluations.unwrap_or(saved.info.iteration_count),
+            seconds,
+            history: saved.history,
+        })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ResultsError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn load_all<P: AsRef<Path>>(paths: &[P]) -> Result<Vec<Self>, ResultsError> {
+        let mut files: Vec<PathBuf> = Vec::new();
+        for path in paths.iter().map(|x| x.as_ref()) {
+            if path.is_dir() {
+                for entry in std::fs::read_dir(path)? {
+                    let path = entry?.path();
+                    if path.is_file() && path.extension().is_some_and(|x| x == "json") { files.push(path); }
+                }
+            } else {
+                files.push(path.to_path_buf());
+            }
+        }
+        files.sort();
+
+        let mut results = Vec::with_capacity(files.len());
+        for file in files {
+            let json = std::fs::read_to_string(&file)?;
+            let value: serde_json::Value = serde_json::from_str(&json)?;
+            if value.get("info").is_none() { continue; }
+            results.push(Self::from_json(&json)?);
+        }
+        Ok(results)
+    }
+}
+
+fn parse_timetaken(timetaken: &str) -> f64 {
+    timetaken.trim_start_matches("PT").trim_end_matches('S').parse().unwrap_or(0.0)
+}
+
+pub(crate) fn csv(field: &str) -> String {
+    if field.contains(',') || field.contains('"') { format!("\"{}\"", field.replace('"', "\"\"")) } else { String::from(field) }
+}
@@ -10,23 +10,25 @@ use std::thread;
 use futures::channel::mpsc;
 
 
-pub struct RandomSampleThreaded { instance: Instance }
+pub struct RandomSampleThreaded { instance: Arc<Instance>, config: RunConfig, seed: u64 }
 
 impl RandomSampleThreaded {
-    pub fn new(instance: &Instance) -> Self {
-        Self { instance: instance.clone() }
+    pub fn new(instance: Arc<Instance>, config: RunConfig) -> Self {
+        let seed = config.seed.unwrap_or_else(|| StdRng::from_entropy().gen());
+        Self { instance, config, seed }
     }
+
     pub fn solve(&self) -> BlackBox {
         let available_threads_count =
-            std::thread::available_concurrency().map(|x| x.get()).unwrap_or(1);
+            std::thread::available_parallelism().map(|x| x.get()).unwrap_or(1);
 
         println!("Thread Count: {}", available_threads_count);
-        let handles = (0..available_threads_count).map(|_| {
-            let inst = self.instance.clone();
-            std::thread::spawn(|| RandomSample::new(inst).solve())
+        let handles = derive_seeds(self.seed, available_threads_count).into_iter().map(|seed| {
+            let rs = RandomSample::new(self.instance.clone(), RunConfig { seed: Some(seed), ..self.config.clone() });
+            std::thread::spawn(move || rs.solve())
         }).collect_vec();
 
-        let bbs = handles.into_iter().map(|x| (x.thread().id().as_u64().get(), x.join().unwrap())).collect_vec();
+        let bbs = handles.into_iter().enumerate().map(|(id, x)| (id, x.join().unwrap())).collect_vec();
 
         for (id, bb) in bbs.iter() {
             println!("Thread ID: {}", id);
@@ -38,7 +40,7 @@ impl RandomSampleThreaded {
 
 
         let best_solution =
-            bbs.into_iter().max_by_key(|(_, bb)| bb.best_candidate.makespan).unwrap();
+            bbs.into_iter().min_by_key(|(_, bb)| bb.best_candidate.makespan).unwrap();
         println!("Best Thread: ID {} : Makespan {}", best_solution.0, best_solution.1.best_candidate.makespan);
         best_solution.1
     }
@@ -47,8 +49,8 @@ impl RandomSampleThreaded {
 pub struct RandomSample { process: BlackBox }
 
 impl RandomSample {
-    pub fn new(instance: Instance) -> Self {
-        Self { process: BlackBox::new(instance, String::from("random sample")) }
+    pub fn new(instance: Arc<Instance>, config: RunConfig) -> Self {
+        Self { process: BlackBox::new(instance, String::from("random sample"), config) }
     }
 
     pub fn solve(&self) -> BlackBox {
@@ -57,11 +59,11 @@ impl RandomSample {
         let mut best_solution = solution.clone();
 
 
-        while !(process.should_terminate)(&mut process) {
+        while !process.should_terminate() {
             solution = <BlackBox as NullaryOperator>::apply(&mut process);
-            process.update_history(&best_solution);
 
             if solution > best_solution { best_solution = solution }
+            process.update_history(&best_solution);
         }
 
         process.update(&best_solution);
@@ -69,9 +71,11 @@ impl RandomSample {
     }
 
     pub fn solve_threaded(&self) -> BlackBox {
-        let handles = (0..thread::available_concurrency().expect("Failed to get thread count").get())
-            .map(|_| {
-                let rs = RandomSample::new(self.process.instance.clone());
+        let thread_count = thread::available_parallelism().expect("Failed to get thread count").get();
+        let handles = derive_seeds(self.process.seed, thread_count).into_iter()
+            .map(|seed| {
+                let config = RunConfig { seed: Some(seed), ..self.process.config.clone() };
+                let rs = RandomSample::new(self.process.instance.clone(), config);
                 thread::spawn(move || rs.solve())
             })
             .collect_vec();
@@ -83,21 +87,22 @@ impl RandomSample {
 
         println!("Used {} threads", bbs.len());
         println!("With {} total iterations", bbs.iter().fold(0, |a, b| a + b.termination_counter));
-        println!("In the time of {}s", self.process.instance.termination_limit);
-        bbs.into_iter().max_by_key(|x| x.best_candidate.makespan).unwrap().clone()
+        println!("Until {}", self.process.config.termination);
+        bbs.into_iter().min_by_key(|x| x.best_candidate.makespan).unwrap()
     }
 
     pub async fn solve_async(&mut self, async_count: usize) -> BlackBox {
         let mut process = self.process.clone();
 
         // MPSC
-        let (mut tx, mut rx) = mpsc::channel(20);
-        (0..async_count).for_each(|_| {
+        let (tx, mut rx) = mpsc::channel(20);
+        derive_seeds(self.process.seed, async_count).into_iter().for_each(|seed| {
             let mut tx = tx.clone();
             let mut gen_process = self.process.clone();
+            gen_process.reseed(seed);
             thread::spawn(move || {
                 while !tx.is_closed() {
-                    tx.start_send(<BlackBox as NullaryOperator>::apply(&mut gen_process));
+                    let _ = tx.start_send(<BlackBox as NullaryOperator>::apply(&mut gen_process));
                 }
             });
         });
@@ -105,11 +110,11 @@ impl RandomSample {
         let mut solution: Candidate = rx.next().await.expect("Failed to get next Candidate");
         let mut best_solution = solution.clone();
 
-        while !(self.process.should_terminate)(&mut process) {
+        while !process.should_terminate() {
             solution = rx.next().await.expect("Failed to get next Candidate");
 
-            process.update_history(&best_solution);
             if solution > best_solution { best_solution = solution }
+            process.update_history(&best_solution);
         }
 
         process.update(&best_solution);
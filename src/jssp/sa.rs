@@ -1,4 +1,33 @@
 use crate::jssp::*;
+use std::str::FromStr;
+
+/// Cooling schedule of the annealing temperature over the iterations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Temperature {
+    Exponential,
+    Logarithmic,
+}
+
+impl Display for Temperature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Temperature::Exponential => write!(f, "exponential"),
+            Temperature::Logarithmic => write!(f, "logarithmic"),
+        }
+    }
+}
+
+impl FromStr for Temperature {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "exponential" => Ok(Temperature::Exponential),
+            "logarithmic" => Ok(Temperature::Logarithmic),
+            _ => Err(format!("Unsupported temperature {}, expected one of exponential, logarithmic", s)),
+        }
+    }
+}
 
 pub struct SimulatedAnnealing {
     process: BlackBox,
@@ -7,30 +36,29 @@ pub struct SimulatedAnnealing {
 }
 
 impl SimulatedAnnealing {
-    pub fn new(instance: Instance, annealing_speed: f64, start_temperature: f64) -> Self {
+    pub fn new(instance: Arc<Instance>, annealing_speed: f64, start_temperature: f64, config: RunConfig) -> Self {
         Self {
-            process: BlackBox::new(instance, String::from("Simulated Annealing")),
+            process: BlackBox::new(instance, String::from("Simulated Annealing"), config),
             temperature_start: start_temperature,
             annealing_speed,
         }
     }
 
-    pub fn solve(&mut self, temperature_operator: &str) -> BlackBox {
+    pub fn solve(&mut self, temperature: Temperature) -> BlackBox {
         let mut best_solution: Candidate = <BlackBox as NullaryOperator>::apply(&mut self.process);
         let mut curr: Candidate = best_solution.clone();
         let mut next: Candidate;
 
-        let temperature_op: fn(&Self) -> f64 = match temperature_operator {
-            "exponential" => <Self as TemperatureSchedule<Exponential>>::temperature,
-            "logarithmic" => <Self as TemperatureSchedule<Logarithmic>>::temperature,
-            _ => panic!("Unsupported temperature operator"),
+        let temperature_op: fn(&Self) -> f64 = match temperature {
+            Temperature::Exponential => <Self as TemperatureSchedule<Exponential>>::temperature,
+            Temperature::Logarithmic => <Self as TemperatureSchedule<Logarithmic>>::temperature,
         };
 
-        while !(self.process.should_terminate)(&mut self.process) {
+        while !self.process.should_terminate() {
             next = <BlackBox as UnaryOperatorNSwap>::apply(&mut self.process, &curr);
             if next.makespan <= curr.makespan
                 || self.process.random.gen_bool(((curr.makespan as i32 - next.makespan as i32) as f64
-                / temperature_op(&self)).exp()) { curr = next; }
+                / temperature_op(self)).exp()) { curr = next; }
 
             if curr > best_solution {
                 best_solution = curr.clone();
@@ -39,8 +67,7 @@ impl SimulatedAnnealing {
         }
 
         self.process.update(&best_solution);
-        let name = format!("annealing_{}", temperature_operator.to_lowercase());
-        self.process.clone()
+        self.process.clone().finalize()
     }
 }
 
@@ -61,4 +88,19 @@ impl TemperatureSchedule<Logarithmic> for SimulatedAnnealing {
     fn temperature(&self) -> f64 {
         self.temperature_start / ((self.process.termination_counter - 1) as f64 * self.annealing_speed + 1f64.exp()).ln()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::experiment::Algorithm;
+    use crate::jssp::sa::Temperature;
+
+    #[test]
+    fn parses_temperatures() {
+        assert_eq!("Logarithmic".parse::<Temperature>(), Ok(Temperature::Logarithmic));
+        assert_eq!(Temperature::Exponential.to_string(), "exponential");
+        assert!("foo".parse::<Temperature>().is_err());
+        assert!("sa:temperature=foo".parse::<Algorithm>().is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::jssp::*;
+
+struct TaillardRandom { seed: i64 }
+
+impl TaillardRandom {
+    const A: i64 = 16_807;
+    const B: i64 = 127_773;
+    const C: i64 = 2_836;
+    const M: i64 = 2_147_483_647;
+
+    fn new(seed: usize) -> Self { Self { seed: seed as i64 } }
+
+    fn unif(&mut self, low: usize, high: usize) -> usize {
+        let k = self.seed / Self::B;
+        self.seed = Self::A * (self.seed % Self::B) - k * Self::C;
+        if self.seed < 0 { self.seed += Self::M; }
+
+        let value = self.seed as f64 / Self::M as f64;
+        low + (value * (high - low + 1) as f64) as usize
+    }
+}
+
+pub fn generate(n: usize, m: usize, time_seed: usize, machine_seed: usize) -> Vec<Vec<usize>> {
+    let mut time_random = TaillardRandom::new(time_seed);
+    let times: Vec<Vec<usize>> = (0..n)
+        .map(|_| (0..m).map(|_| time_random.unif(1, 99)).collect())
+        .collect();
+
+    let mut machine_random = TaillardRandom::new(machine_seed);
+    let machines: Vec<Vec<usize>> = (0..n).map(|_| {
+        let mut machines: Vec<usize> = (0..m).collect();
+        for j in 0..m { machines.swap(j, machine_random.unif(j, m - 1)); }
+        machines
+    }).collect();
+
+    times.into_iter().zip(machines).map(|(x, y)| y.into_iter().interleave(x).collect()).collect()
+}
+
+impl Instance {
+    pub fn generate(name: &str, n: usize, m: usize, time_seed: usize, machine_seed: usize) -> Self {
+        Self {
+            jobs: generate(n, m, time_seed, machine_seed),
+            name: String::from(name),
+            type_: InstanceType::TAILLARD,
+            seeds: Some((time_seed, machine_seed)),
+            best_known: None,
+            known_lower_bound: None,
+            m,
+            n,
+        }
+    }
+
+    pub fn to_taillard(&self) -> String {
+        let (time_seed, machine_seed) = self.seeds.unwrap_or((0, 0));
+        let (upper_bound, lower_bound) = (self.best_known.unwrap_or(0), self.known_lower_bound.unwrap_or(0));
+
+        let mut lines = vec![
+            String::from("Nb of jobs, Nb of Machines, Time seed, Machine seed, Upper bound, Lower bound"),
+            format!("{:>10}{:>10}{:>11}{:>11}{:>10}{:>10}", self.n, self.m, time_seed, machine_seed, upper_bound, lower_bound),
+            String::from("Times"),
+        ];
+        lines.extend(self.jobs.iter().map(|x| (1..x.len()).step_by(2).map(|i| format!("{:>3}", x[i])).join("")));
+        lines.push(String::from("Machines"));
+        lines.extend(self.jobs.iter().map(|x| (0..x.len()).step_by(2).map(|i| format!("{:>3}", x[i] + 1)).join("")));
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+
+    #[test]
+    fn regenerates_shipped_instances() {
+        for name in ["ta01", "ta02", "ta03", "ta04"].iter() {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("instances").join(format!("{}.txt", name));
+            let contents = std::fs::read_to_string(&path).expect("Failed to read the instance");
+
+            let header: Vec<usize> = contents.lines().nth(1).expect("Failed to read the header")
+                .split_whitespace().map(|x| x.parse().unwrap()).collect();
+            let mut instance = Instance::generate(name, header[0], header[1], header[2], header[3]);
+            instance.best_known = Some(header[4]);
+            instance.known_lower_bound = Some(header[5]);
+
+            assert_eq!(instance.to_taillard(), contents, "{} differs", name);
+        }
+    }
+}
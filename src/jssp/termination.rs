@@ -0,0 +1,144 @@
+use crate::jssp::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+#[derive(Clone)]
+pub enum Termination {
+    Evaluations(usize),
+    Time(Duration),
+    Target(usize),
+    Stagnation(usize),
+    StagnationTime(Duration),
+    Cancel(Arc<AtomicBool>),
+    All(Vec<Termination>),
+    Any(Vec<Termination>),
+}
+
+impl Termination {
+    pub fn evaluations(limit: usize) -> Self { Termination::Evaluations(limit) }
+
+    pub fn seconds(limit: f64) -> Self { Termination::Time(Duration::from_secs_f64(limit)) }
+
+    pub fn target(makespan: usize) -> Self { Termination::Target(makespan) }
+
+    pub fn stagnation(evaluations: usize) -> Self { Termination::Stagnation(evaluations) }
+
+    pub fn stagnation_seconds(limit: f64) -> Self { Termination::StagnationTime(Duration::from_secs_f64(limit)) }
+
+    pub fn cancel(flag: Arc<AtomicBool>) -> Self { Termination::Cancel(flag) }
+
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Termination::All(mut criteria) => {
+                criteria.push(other);
+                Termination::All(criteria)
+            }
+            criterion => Termination::All(vec![criterion, other]),
+        }
+    }
+
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Termination::Any(mut criteria) => {
+                criteria.push(other);
+                Termination::Any(criteria)
+            }
+            criterion => Termination::Any(vec![criterion, other]),
+        }
+    }
+
+    pub(crate) fn is_met(&self, process: &BlackBox) -> bool {
+        match self {
+            Termination::Evaluations(limit) => process.evaluations >= *limit,
+            Termination::Time(limit) => process.timer.elapsed() >= *limit,
+            Termination::Target(makespan) => process.best_makespan <= *makespan,
+            Termination::Stagnation(limit) => process.evaluations.saturating_sub(process.improved_at.0) >= *limit,
+            Termination::StagnationTime(limit) =>
+                process.timer.elapsed().as_secs_f64() - process.improved_at.1 >= limit.as_secs_f64(),
+            Termination::Cancel(flag) => flag.load(Ordering::Relaxed),
+            Termination::All(criteria) => criteria.iter().all(|x| x.is_met(process)),
+            Termination::Any(criteria) => criteria.iter().any(|x| x.is_met(process)),
+        }
+    }
+}
+
+impl Display for Termination {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Termination::Evaluations(limit) => write!(f, "{} evaluations", limit),
+            Termination::Time(limit) => write!(f, "{}s", limit.as_secs_f64()),
+            Termination::Target(makespan) => write!(f, "makespan {}", makespan),
+            Termination::Stagnation(limit) => write!(f, "{} evaluations without improvement", limit),
+            Termination::StagnationTime(limit) => write!(f, "{}s without improvement", limit.as_secs_f64()),
+            Termination::Cancel(_) => write!(f, "cancel"),
+            Termination::All(criteria) => write!(f, "({})", criteria.iter().join(" and ")),
+            Termination::Any(criteria) => write!(f, "({})", criteria.iter().join(" or ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::ga::{Crossover, Genetic};
+    use crate::jssp::rs::RandomSample;
+
+    #[test]
+    fn evaluations_count_decoded_candidates() {
+        let instance = test_instance();
+        let config = RunConfig::new(Termination::evaluations(500)).with_seed(1);
+
+        let result = RandomSample::new(instance.clone(), config.clone()).solve();
+        assert_eq!(result.evaluations, 500);
+
+        let result = Genetic::new(instance, config).solve(Crossover::Ppx, 0.5, 16, 16).unwrap();
+        assert!(result.evaluations >= 500 && result.evaluations < 500 + 16, "{} evaluations", result.evaluations);
+        assert!(result.termination_counter < result.evaluations);
+    }
+
+    #[test]
+    fn and_or_nest_into_all_any() {
+        let all = Termination::evaluations(10).and(Termination::target(5)).and(Termination::stagnation(3));
+        assert!(matches!(&all, Termination::All(criteria) if criteria.len() == 3));
+        let any = Termination::evaluations(10).or(Termination::target(5)).or(Termination::stagnation(3));
+        assert!(matches!(&any, Termination::Any(criteria) if criteria.len() == 3));
+        assert_eq!(Termination::evaluations(10).or(Termination::target(5)).to_string(), "(10 evaluations or makespan 5)");
+    }
+
+    #[test]
+    fn all_and_any_combine_criteria() {
+        let mut process = test_process(1);
+        process.evaluations = 20;
+        process.best_makespan = 100;
+
+        let (reached, missed) = (Termination::evaluations(20), Termination::target(99));
+        assert!(reached.is_met(&process));
+        assert!(!missed.is_met(&process));
+        assert!(!reached.clone().and(missed.clone()).is_met(&process));
+        assert!(reached.clone().or(missed.clone()).is_met(&process));
+        assert!(Termination::All(vec![]).is_met(&process));
+        assert!(!Termination::Any(vec![]).is_met(&process));
+        assert!(Termination::target(100).and(reached).is_met(&process));
+    }
+
+    #[test]
+    fn stagnation_counts_evaluations_since_the_last_improvement() {
+        let mut process = test_process(1);
+        process.evaluations = 40;
+        let candidate = <BlackBox as NullaryOperator>::apply(&mut process);
+        process.update_history(&candidate);
+        assert_eq!(process.improved_at.0, 41);
+
+        process.evaluations = 50;
+        assert!(!Termination::stagnation(10).is_met(&process));
+        process.evaluations = 51;
+        assert!(Termination::stagnation(10).is_met(&process));
+
+        let better = Candidate { makespan: candidate.makespan - 1, ..candidate };
+        process.update_history(&better);
+        assert!(!Termination::stagnation(10).is_met(&process));
+
+        process.evaluations = 0;
+        assert!(!Termination::stagnation(10).is_met(&process));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::jssp::*;
+use crate::jssp::graph::DisjunctiveGraph;
+use crate::jssp::neighbourhood::{Move, Neighbourhood};
+use std::collections::VecDeque;
+
+/// `(machine, before, after)`: jobs `before` and `after` on `machine` whose order a move reversed.
+type Pair = (usize, usize, usize);
+
+pub struct TabuSearch {
+    process: BlackBox,
+    tenure: usize,
+    neighbourhood: Neighbourhood,
+    tabu: VecDeque<Vec<Pair>>,
+}
+
+impl TabuSearch {
+    /// Moves that would restore a job order reversed by one of the last `tenure` moves are tabu.
+    pub fn new(instance: Arc<Instance>, tenure: usize, neighbourhood: Neighbourhood, config: RunConfig) -> Self {
+        Self {
+            process: BlackBox::new(instance, String::from("Tabu Search"), config),
+            tenure,
+            neighbourhood,
+            tabu: VecDeque::with_capacity(tenure + 1),
+        }
+    }
+
+    pub fn solve(&mut self) -> BlackBox {
+        let mut best_candidate: Candidate = <BlackBox as NullaryOperator>::apply(&mut self.process);
+        let mut curr_candidate: Candidate = best_candidate.clone();
+        self.process.update_history(&best_candidate);
+
+        let mut terminated = false;
+        while !terminated {
+            let graph = self.process.graph(&curr_candidate);
+            let moves = self.neighbourhood.moves(&graph);
+            if moves.is_empty() { break; }
+
+            let mut allowed: Option<(Move, Candidate)> = None;
+            let mut fallback: Option<(Move, Candidate)> = None;
+            for mv in moves {
+                if self.process.should_terminate() {
+                    terminated = true;
+                    break;
+                }
+                let next_candidate = self.process.apply_move(&graph, mv);
+
+                if (!self.is_tabu(&graph, mv) || next_candidate > best_candidate)
+                    && allowed.as_ref().is_none_or(|(_, x)| next_candidate > *x) {
+                    allowed = Some((mv, next_candidate.clone()));
+                }
+                if fallback.as_ref().is_none_or(|(_, x)| next_candidate > *x) {
+                    fallback = Some((mv, next_candidate));
+                }
+            }
+
+            let (mv, next_candidate) = match allowed.or(fallback) {
+                Some(chosen) => chosen,
+                None => break,
+            };
+            self.make_tabu(&graph, mv);
+            curr_candidate = next_candidate;
+
+            if curr_candidate > best_candidate {
+                best_candidate = curr_candidate.clone();
+                self.process.update_history(&best_candidate);
+            }
+        }
+
+        self.process.update(&best_candidate);
+        self.process.clone().finalize()
+    }
+
+    /// A swap reverses one pair; a shift reverses the moved job with every job it jumps over.
+    fn reversed_pairs(graph: &DisjunctiveGraph, mv: Move) -> Vec<Pair> {
+        let job = |machine: usize, position: usize| graph.job(graph.sequences()[machine][position]);
+        match mv {
+            Move::Swap { machine, position } => vec![(machine, job(machine, position), job(machine, position + 1))],
+            Move::Shift { machine, from, to } if from < to =>
+                (from + 1..=to).map(|x| (machine, job(machine, from), job(machine, x))).collect(),
+            Move::Shift { machine, from, to } => (to..from).map(|x| (machine, job(machine, x), job(machine, from))).collect(),
+        }
+    }
+
+    fn is_tabu(&self, graph: &DisjunctiveGraph, mv: Move) -> bool {
+        Self::reversed_pairs(graph, mv).into_iter()
+            .any(|(machine, a, b)| self.tabu.iter().flatten().any(|&x| x == (machine, b, a)))
+    }
+
+    fn make_tabu(&mut self, graph: &DisjunctiveGraph, mv: Move) {
+        self.tabu.push_back(Self::reversed_pairs(graph, mv));
+        while self.tabu.len() > self.tenure { self.tabu.pop_front(); }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::neighbourhood::{Move, Neighbourhood};
+    use crate::jssp::ts::TabuSearch;
+
+    #[test]
+    fn shifts_reverse_every_jumped_pair() {
+        let mut ts = TabuSearch::new(test_instance(), 8, Neighbourhood::N6, RunConfig::new(Termination::evaluations(0)).with_seed(2));
+        let candidate = <BlackBox as NullaryOperator>::apply(&mut ts.process);
+        let graph = ts.process.graph(&candidate);
+        let jobs = graph.sequences()[3].iter().map(|&x| graph.job(x)).collect_vec();
+
+        assert_eq!(TabuSearch::reversed_pairs(&graph, Move::Swap { machine: 3, position: 1 }), vec![(3, jobs[1], jobs[2])]);
+        assert_eq!(TabuSearch::reversed_pairs(&graph, Move::Shift { machine: 3, from: 1, to: 3 }),
+                   vec![(3, jobs[1], jobs[2]), (3, jobs[1], jobs[3])]);
+        assert_eq!(TabuSearch::reversed_pairs(&graph, Move::Shift { machine: 3, from: 3, to: 1 }),
+                   vec![(3, jobs[1], jobs[3]), (3, jobs[2], jobs[3])]);
+    }
+
+    #[test]
+    fn undoing_a_move_is_tabu() {
+        let mut ts = TabuSearch::new(test_instance(), 8, Neighbourhood::N6, RunConfig::new(Termination::evaluations(0)).with_seed(2));
+        let candidate = <BlackBox as NullaryOperator>::apply(&mut ts.process);
+        let graph = ts.process.graph(&candidate);
+
+        for (mv, undo) in [(Move::Swap { machine: 0, position: 2 }, Move::Swap { machine: 0, position: 2 }),
+                           (Move::Shift { machine: 0, from: 1, to: 4 }, Move::Shift { machine: 0, from: 4, to: 1 }),
+                           (Move::Shift { machine: 0, from: 4, to: 1 }, Move::Shift { machine: 0, from: 1, to: 4 })].iter() {
+            ts.tabu.clear();
+            ts.make_tabu(&graph, *mv);
+            let mut next = graph.clone();
+            mv.apply(&mut next);
+            assert!(ts.is_tabu(&next, *undo), "undoing {:?} is allowed", mv);
+            assert!(!ts.is_tabu(&next, Move::Swap { machine: 1, position: 0 }));
+        }
+    }
+
+    #[test]
+    fn tenure_limits_the_tabu_list() {
+        let mut ts = TabuSearch::new(test_instance(), 2, Neighbourhood::N1, RunConfig::new(Termination::evaluations(0)).with_seed(2));
+        let candidate = <BlackBox as NullaryOperator>::apply(&mut ts.process);
+        let graph = ts.process.graph(&candidate);
+        for machine in 0..3 { ts.make_tabu(&graph, Move::Swap { machine, position: 0 }); }
+        assert_eq!(ts.tabu.len(), 2);
+        assert!(ts.tabu.iter().all(|x| x[0].0 != 0));
+    }
+
+    #[test]
+    fn solves_with_every_neighbourhood() {
+        let instance = test_instance();
+        for &neighbourhood in [Neighbourhood::N1, Neighbourhood::N5, Neighbourhood::N6].iter() {
+            let config = RunConfig::new(Termination::evaluations(2_000)).with_seed(4);
+            let result = TabuSearch::new(instance.clone(), 8, neighbourhood, config).solve();
+
+            assert_eq!(instance.validate_candidate(&result.best_candidate), vec![], "{}", neighbourhood);
+            assert!(result.history.windows(2).all(|x| x[1].1 <= x[0].1));
+            assert!(result.best_candidate.makespan <= result.history[0].1);
+        }
+    }
+}
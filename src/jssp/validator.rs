@@ -0,0 +1,220 @@
+use crate::jssp::*;
+use serde::Deserialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum Violation {
+    MachineCount { expected: usize, found: usize },
+    MalformedRow { machine: usize, length: usize },
+    UnknownJob { machine: usize, job: usize },
+    UnexpectedOperation { job: usize, machine: usize },
+    DuplicateOperation { job: usize, machine: usize },
+    MissingOperation { job: usize, machine: usize },
+    WrongDuration { job: usize, machine: usize, expected: usize, found: usize },
+    Precedence { job: usize, machine: usize, start: usize, previous_machine: usize, previous_end: usize },
+    Overlap { machine: usize, first_job: usize, second_job: usize },
+    WrongMakespan { expected: usize, found: usize },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Violation::MachineCount { expected, found } =>
+                write!(f, "Expected {} machines, found {}", expected, found),
+            Violation::MalformedRow { machine, length } =>
+                write!(f, "Machine {} has a row of length {} which is not made of triples", machine, length),
+            Violation::UnknownJob { machine, job } =>
+                write!(f, "Machine {} processes an unknown job {}", machine, job),
+            Violation::UnexpectedOperation { job, machine } =>
+                write!(f, "Job {} has no operation on machine {}", job, machine),
+            Violation::DuplicateOperation { job, machine } =>
+                write!(f, "Job {} is processed more than once on machine {}", job, machine),
+            Violation::MissingOperation { job, machine } =>
+                write!(f, "Job {} is never processed on machine {}", job, machine),
+            Violation::WrongDuration { job, machine, expected, found } =>
+                write!(f, "Job {} on machine {} takes {} instead of {}", job, machine, found, expected),
+            Violation::Precedence { job, machine, start, previous_machine, previous_end } =>
+                write!(f, "Job {} starts on machine {} at {} before finishing on machine {} at {}",
+                       job, machine, start, previous_machine, previous_end),
+            Violation::Overlap { machine, first_job, second_job } =>
+                write!(f, "Jobs {} and {} overlap on machine {}", first_job, second_job, machine),
+            Violation::WrongMakespan { expected, found } =>
+                write!(f, "Reported makespan {} differs from the schedule's {}", found, expected),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SavedSolution { solution: SavedCandidate }
+
+#[derive(Deserialize)]
+struct SavedCandidate {
+    makespan: usize,
+    schedule: Vec<Vec<usize>>,
+}
+
+impl Instance {
+    pub fn validate(&self, schedule: &CandidateSchedule) -> Vec<Violation> {
+        self.validate_schedule(&schedule.schedule, None)
+    }
+
+    pub fn validate_candidate(&self, candidate: &Candidate) -> Vec<Violation> {
+        self.validate_schedule(&candidate.schedule, Some(candidate.makespan))
+    }
+
+    pub fn validate_json(&self, json: &str) -> serde_json::Result<Vec<Violation>> {
+        let saved: SavedSolution = serde_json::from_str(json)?;
+        Ok(self.validate_schedule(&saved.solution.schedule, Some(saved.solution.makespan)))
+    }
+
+    fn validate_schedule(&self, schedule: &[Vec<usize>], makespan: Option<usize>) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        if schedule.len() != self.m {
+            violations.push(Violation::MachineCount { expected: self.m, found: schedule.len() });
+        }
+
+        let mut times: Vec<Vec<Option<(usize, usize)>>> = vec![vec![None; self.m]; self.n];
+        for (machine, row) in schedule.iter().enumerate().take(self.m) {
+            if row.len() % 3 != 0 {
+                violations.push(Violation::MalformedRow { machine, length: row.len() });
+            }
+
+            let mut operations: Vec<(usize, usize, usize)> = Vec::with_capacity(row.len() / 3);
+            for operation in row.chunks_exact(3) {
+                let (job, start, end) = (operation[0], operation[1], operation[2]);
+                if job >= self.n {
+                    violations.push(Violation::UnknownJob { machine, job });
+                    continue;
+                }
+
+                let step = match (0..self.m).find(|&x| self.jobs[job][2 * x] == machine) {
+                    Some(step) => step,
+                    None => {
+                        violations.push(Violation::UnexpectedOperation { job, machine });
+                        continue;
+                    }
+                };
+                if times[job][step].is_some() {
+                    violations.push(Violation::DuplicateOperation { job, machine });
+                    continue;
+                }
+                let expected = self.jobs[job][2 * step + 1];
+                if end < start || end - start != expected {
+                    violations.push(Violation::WrongDuration { job, machine, expected, found: end.saturating_sub(start) });
+                }
+
+                times[job][step] = Some((start, end));
+                operations.push((start, end, job));
+            }
+
+            operations.sort();
+            for (a, b) in operations.iter().tuple_windows() {
+                if b.0 < a.1 { violations.push(Violation::Overlap { machine, first_job: a.2, second_job: b.2 }); }
+            }
+        }
+
+        for (job, job_times) in times.iter().enumerate() {
+            for step in 0..self.m {
+                let machine = self.jobs[job][2 * step];
+                let (start, _) = match job_times[step] {
+                    Some(time) => time,
+                    None => {
+                        violations.push(Violation::MissingOperation { job, machine });
+                        continue;
+                    }
+                };
+                if let Some(previous) = (0..step).rev().find(|&x| job_times[x].is_some()) {
+                    let (previous_machine, previous_end) = (self.jobs[job][2 * previous], job_times[previous].unwrap().1);
+                    if start < previous_end {
+                        violations.push(Violation::Precedence { job, machine, start, previous_machine, previous_end });
+                    }
+                }
+            }
+        }
+
+        if let Some(found) = makespan {
+            let expected = times.iter().flatten().flatten().map(|&(_, end)| end).max().unwrap_or(0);
+            if found != expected { violations.push(Violation::WrongMakespan { expected, found }); }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::validator::Violation;
+
+    /// Job 0 runs on machine 0 for 3 then machine 1 for 2; job 1 on machine 1 for 2 then machine 0 for 4.
+    fn instance() -> Instance {
+        Instance::load_reader("tiny", "2 2\n0 3 1 2\n1 2 0 4\n".as_bytes()).unwrap()
+    }
+
+    fn valid() -> Vec<Vec<usize>> {
+        vec![vec![0, 0, 3, 1, 3, 7], vec![1, 0, 2, 0, 3, 5]]
+    }
+
+    fn check(schedule: Vec<Vec<usize>>) -> Vec<Violation> {
+        instance().validate(&CandidateSchedule { schedule })
+    }
+
+    #[test]
+    fn accepts_a_valid_schedule() {
+        let candidate = Candidate { makespan: 7, order: vec![0, 1, 1, 0], schedule: valid() };
+        assert_eq!(instance().validate_candidate(&candidate), vec![]);
+    }
+
+    #[test]
+    fn accepts_decoded_candidates() {
+        let instance = test_instance();
+        for &decoder in [Decoder::SemiActive, Decoder::Active].iter() {
+            let config = RunConfig::new(Termination::evaluations(0)).with_seed(11).with_decoder(decoder);
+            let mut process = BlackBox::new(instance.clone(), String::from("test"), config);
+            for _ in 0..20 {
+                let mut candidate = <BlackBox as NullaryOperator>::apply(&mut process);
+                candidate.materialize(&mut process);
+                assert_eq!(instance.validate_candidate(&candidate), vec![], "{} decoder", decoder);
+            }
+        }
+    }
+
+    #[test]
+    fn finds_machine_overlap() {
+        let mut schedule = valid();
+        schedule[0] = vec![0, 0, 3, 1, 2, 6];
+        assert_eq!(check(schedule), vec![Violation::Overlap { machine: 0, first_job: 0, second_job: 1 }]);
+    }
+
+    #[test]
+    fn finds_job_precedence() {
+        let mut schedule = valid();
+        schedule[1] = vec![1, 0, 2, 0, 2, 4];
+        assert_eq!(check(schedule), vec![Violation::Precedence { job: 0, machine: 1, start: 2, previous_machine: 0, previous_end: 3 }]);
+    }
+
+    #[test]
+    fn finds_missing_operation() {
+        let mut schedule = valid();
+        schedule[1] = vec![1, 0, 2];
+        assert_eq!(check(schedule), vec![Violation::MissingOperation { job: 0, machine: 1 }]);
+    }
+
+    #[test]
+    fn finds_duplicate_operation() {
+        let mut schedule = valid();
+        schedule[0].extend_from_slice(&[0, 7, 10]);
+        assert_eq!(check(schedule), vec![Violation::DuplicateOperation { job: 0, machine: 0 }]);
+    }
+
+    #[test]
+    fn finds_wrong_duration() {
+        let mut schedule = valid();
+        schedule[0][5] = 8;
+        assert_eq!(check(schedule), vec![Violation::WrongDuration { job: 1, machine: 0, expected: 4, found: 5 }]);
+    }
+
+    #[test]
+    fn finds_wrong_makespan() {
+        let candidate = Candidate { makespan: 8, order: vec![0, 1, 1, 0], schedule: valid() };
+        assert_eq!(instance().validate_candidate(&candidate), vec![Violation::WrongMakespan { expected: 7, found: 8 }]);
+    }
+}
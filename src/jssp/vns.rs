@@ -0,0 +1,155 @@
+use crate::jssp::*;
+use crate::jssp::neighbourhood::Neighbourhood;
+use std::str::FromStr;
+
+/// A neighbourhood structure: a registered unary operator sampled at random, or a critical-block
+/// neighbourhood searched exhaustively.
+#[derive(Clone, Copy, Debug)]
+pub enum Structure {
+    Operator(&'static str, UnaryOperator),
+    Block(Neighbourhood),
+}
+
+impl Display for Structure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Structure::Operator(name, _) => write!(f, "{}", name),
+            Structure::Block(neighbourhood) => write!(f, "{}", neighbourhood),
+        }
+    }
+}
+
+impl FromStr for Structure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(neighbourhood) = s.parse::<Neighbourhood>() { return Ok(Structure::Block(neighbourhood)); }
+
+        let operator = unary_operator(s).map_err(|_| format!("Unsupported neighbourhood {}, expected one of {}, n1, n5, n6",
+                                                             s, unary_operator_names().join(", ")))?;
+        let name = unary_operator_names().into_iter().find(|x| x.eq_ignore_ascii_case(s)).unwrap();
+        Ok(Structure::Operator(name, operator))
+    }
+}
+
+pub struct VariableNeighbourhoodSearch {
+    process: BlackBox,
+    structures: Vec<Structure>,
+    patience: usize,
+}
+
+impl VariableNeighbourhoodSearch {
+    /// A random operator counts as exhausted after `patience` samples in a row without improvement.
+    pub fn new(instance: Arc<Instance>, structures: Vec<Structure>, patience: usize, config: RunConfig) -> Self {
+        assert!(!structures.is_empty(), "Expected at least one neighbourhood");
+        Self {
+            process: BlackBox::new(instance, String::from("Variable Neighbourhood Search"), config),
+            structures,
+            patience: max(patience, 1),
+        }
+    }
+
+    /// General VNS: shakes the current candidate in the k-th neighbourhood and descends with VND,
+    /// moving back to the first neighbourhood on improvement and on to the next one otherwise.
+    /// A shake that lands on a new best is recorded as an improvement of its own.
+    pub fn solve(&mut self) -> BlackBox {
+        let mut best_candidate: Candidate = <BlackBox as NullaryOperator>::apply(&mut self.process);
+        self.process.update_history(&best_candidate);
+        let mut curr_candidate = self.descend(best_candidate.clone(), &mut best_candidate);
+
+        let mut k = 0;
+        while !self.process.should_terminate() {
+            let structure = self.structures[k];
+            let shaken = self.shake(&curr_candidate, structure);
+            if shaken > best_candidate {
+                self.process.record_improvement(&curr_candidate, &shaken, format!("{} shake", structure));
+            }
+            let next_candidate = self.descend(shaken, &mut best_candidate);
+
+            if next_candidate > curr_candidate {
+                curr_candidate = next_candidate;
+                k = 0;
+            } else {
+                k = (k + 1) % self.structures.len();
+            }
+        }
+
+        self.process.update(&best_candidate);
+        self.process.clone().finalize()
+    }
+
+    /// Variable neighbourhood descent: improves with the first neighbourhood that can, starting over
+    /// from the first one after every improvement, until none of them improves.
+    fn descend(&mut self, mut candidate: Candidate, best_candidate: &mut Candidate) -> Candidate {
+        let mut k = 0;
+        loop {
+            if candidate > *best_candidate {
+                *best_candidate = candidate.clone();
+                self.process.update_history(best_candidate);
+            }
+            if k == self.structures.len() || self.process.config.termination.is_met(&self.process) { return candidate; }
+
+            let structure = self.structures[k];
+            match self.improve(&candidate, structure) {
+                Some(next_candidate) => {
+                    self.process.record_improvement(&candidate, &next_candidate, structure.to_string());
+                    candidate = next_candidate;
+                    k = 0;
+                }
+                None => k += 1,
+            }
+        }
+    }
+
+    fn improve(&mut self, candidate: &Candidate, structure: Structure) -> Option<Candidate> {
+        match structure {
+            Structure::Block(neighbourhood) => self.process.neighbours(candidate, neighbourhood).into_iter()
+                .map(|(_, x)| x).min_by_key(|x| x.makespan).filter(|x| x > candidate),
+            Structure::Operator(_, operator) => {
+                for _ in 0..self.patience {
+                    if self.process.should_terminate() { break; }
+                    let next_candidate = operator(&mut self.process, candidate);
+                    if next_candidate > *candidate { return Some(next_candidate); }
+                }
+                None
+            }
+        }
+    }
+
+    fn shake(&mut self, candidate: &Candidate, structure: Structure) -> Candidate {
+        match structure {
+            Structure::Operator(_, operator) => operator(&mut self.process, candidate),
+            Structure::Block(neighbourhood) => match self.process.sample_neighbour(candidate, neighbourhood) {
+                Some((_, shaken)) => shaken,
+                None => <BlackBox as UnaryOperatorNSwap>::apply(&mut self.process, candidate),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jssp::*;
+    use crate::jssp::experiment::default_structures;
+    use crate::jssp::vns::{Structure, VariableNeighbourhoodSearch};
+
+    #[test]
+    fn parses_structures() {
+        assert!(matches!("N5".parse::<Structure>(), Ok(Structure::Block(_))));
+        assert!(matches!("Insertion".parse::<Structure>(), Ok(Structure::Operator("insertion", _))));
+        assert_eq!(default_structures().iter().join(","), "n5,n1,n6,1swap,insertion,nswap");
+        assert!("bogus".parse::<Structure>().is_err());
+    }
+
+    #[test]
+    fn records_every_new_best() {
+        let instance = test_instance();
+        for (seed, structures) in [(1, default_structures()), (2, vec!["nswap".parse().unwrap()])].iter() {
+            let config = RunConfig::new(Termination::evaluations(3_000)).with_seed(*seed);
+            let result = VariableNeighbourhoodSearch::new(instance.clone(), structures.clone(), 20, config).solve();
+            assert_eq!(instance.validate_candidate(&result.best_candidate), vec![]);
+            assert!(result.improvements.iter().all(|x| x.to < x.from));
+            assert_eq!(result.improvements.iter().map(|x| x.to).min(), Some(result.best_candidate.makespan));
+        }
+    }
+}
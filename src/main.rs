@@ -1,46 +1,461 @@
-#![feature(available_concurrency)]
-#![feature(thread_id_value)]
+use jobshop_python::jssp;
+use jssp::{Decoder, Instance};
+use jssp::rs::RandomSample;
+use jssp::hc::HillClimber;
+use jssp::termination::Termination;
+use jssp::config::RunConfig;
+use jssp::experiment::{Algorithm, Experiment, RunError};
+use jssp::ga::Crossover;
+use jssp::sa::Temperature;
+use jssp::ils::Acceptance;
+use jssp::vns::Structure;
+use jssp::neighbourhood::Neighbourhood;
+use jssp::results::{RunResult, load_schedule};
+use jssp::gantt;
+use jssp::convergence;
+use jssp::report::Report;
+use jssp::ecdf::{RuntimeAnalysis, Target};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use terminal_size::Width;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+use std::sync::Arc;
 
-pub mod jssp;
+#[derive(Parser)]
+#[command(about = "Job shop scheduling metaheurestics")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
 
-use jssp::rs::RandomSample;
-use jssp::Instance;
-use crate::jssp::rs::RandomSampleThreaded;
-use std::time::{SystemTime, UNIX_EPOCH, Duration};
-use chrono::prelude::*;
-use serde::Serialize;
-use crate::jssp::InstanceType;
-use futures::executor::block_on;
-use rand::{random, Rng};
-use futures::channel::mpsc;
-use futures::{SinkExt, StreamExt, Future};
-use std::thread;
-use futures::channel::mpsc::{TryRecvError, SendError, Receiver};
-use futures::task::{Poll, Context};
-use crate::jssp::hc::HillClimber;
-use itertools::Itertools;
-use std::path::Path;
-use std::fs::File;
-use std::io::Write;
+#[derive(Subcommand)]
+enum Command {
+    /// Solve an instance with the chosen metaheurestic
+    Solve(SolveArgs),
+    /// Run every instance against every algorithm, repeatedly, on all cores
+    Experiment(ExperimentArgs),
+    /// Summarise saved runs and compare the algorithms on each instance
+    Report(ReportArgs),
+    /// Build time-to-target ECDFs and expected running times from saved run histories
+    Ecdf(EcdfArgs),
+    /// Draw a saved solution as a Gantt chart
+    Gantt(GanttArgs),
+    /// Plot best makespan over time for saved runs
+    Convergence(ConvergenceArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Wall-clock limit in seconds
+    #[arg(short, long, conflicts_with = "iterations")]
+    time: Option<usize>,
+    /// Evaluation limit
+    #[arg(short, long)]
+    iterations: Option<usize>,
+    /// Stop once this makespan is reached
+    #[arg(long)]
+    target: Option<usize>,
+    /// Stop after this many evaluations without improvement
+    #[arg(long)]
+    stagnation: Option<usize>,
+    /// Stop after this many seconds without improvement
+    #[arg(long)]
+    stagnation_time: Option<f64>,
+    #[arg(short, long)]
+    seed: Option<u64>,
+    /// Schedule builder: semi-active or active; moves of the n1, n5 and n6 neighbourhoods are always built semi-actively
+    #[arg(long, default_value = "semi-active")]
+    decoder: Decoder,
+    /// Decode the full schedule of every candidate instead of only the best ones
+    #[arg(long)]
+    eager_schedule: bool,
+}
+
+impl RunArgs {
+    fn config(&self) -> RunConfig {
+        let mut termination = match (self.time, self.iterations) {
+            (_, Some(iterations)) => Termination::evaluations(iterations),
+            (Some(time), None) => Termination::seconds(time as f64),
+            (None, None) => Termination::seconds(10.0),
+        };
+        if let Some(target) = self.target { termination = termination.or(Termination::target(target)); }
+        if let Some(stagnation) = self.stagnation { termination = termination.or(Termination::stagnation(stagnation)); }
+        if let Some(stagnation) = self.stagnation_time { termination = termination.or(Termination::stagnation_seconds(stagnation)); }
+
+        let config = RunConfig::new(termination).with_decoder(self.decoder).with_lazy_schedule(!self.eager_schedule);
+        match self.seed {
+            Some(seed) => config.with_seed(seed),
+            None => config,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum AlgorithmName { Rs, Hc, Sa, Ga, Ts, Ils, Vns }
+
+#[derive(Args)]
+struct SolveArgs {
+    /// Instance file, or the name of one in the instances directory
+    instance: String,
+    #[arg(short, long, value_enum, default_value_t = AlgorithmName::Hc)]
+    algorithm: AlgorithmName,
+    #[command(flatten)]
+    run: RunArgs,
+    /// Where to save the solution JSON
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Run one solver per available thread (rs, hc)
+    #[arg(long)]
+    threaded: bool,
+    /// Print the best schedule as a text Gantt chart
+    #[arg(long)]
+    gantt: bool,
+
+    #[arg(long, default_value_t = 1_676)]
+    reset_threshold: usize,
+    #[arg(long, default_value = "nswap")]
+    operator: String,
+
+    #[arg(long, default_value_t = 0.001)]
+    annealing_speed: f64,
+    #[arg(long, default_value_t = 30.0)]
+    start_temperature: f64,
+    /// Cooling schedule of sa: exponential or logarithmic
+    #[arg(long, default_value = "exponential")]
+    temperature: Temperature,
+
+    /// Crossover of the genetic algorithm: ppx, jox, gox, mppx<parents>, e.g. mppx3, or ppx:<mask> of parent indices, e.g. ppx:0110
+    #[arg(long, default_value = "ppx")]
+    crossover: Crossover,
+    #[arg(long, default_value_t = 0.5, value_parser = probability)]
+    crossover_chance: f64,
+    #[arg(long, default_value_t = 32, value_parser = at_least_one)]
+    mu: usize,
+    #[arg(long, default_value_t = 32, value_parser = at_least_one)]
+    lambda: usize,
+
+    #[arg(long, default_value_t = 8)]
+    tenure: usize,
+
+    /// Neighbourhood of ts (default n5) or of the local search in ils (default n1): n1, n5 or n6
+    #[arg(long)]
+    neighbourhood: Option<Neighbourhood>,
+    /// Number of random swaps in an ils kick
+    #[arg(long, default_value_t = 3)]
+    strength: usize,
+    /// Acceptance rule of ils: better, better-or-equal, random-walk or restart<kicks>
+    #[arg(long, default_value = "better")]
+    acceptance: Acceptance,
 
+    /// Neighbourhoods of vns in order, e.g. n5,n1,insertion
+    #[arg(long, value_delimiter = ',', default_value = "n5,n1,n6,1swap,insertion,nswap")]
+    neighbourhoods: Vec<Structure>,
+    /// Samples without improvement before vns leaves a random operator's neighbourhood
+    #[arg(long, default_value_t = 200)]
+    patience: usize,
+}
+
+impl SolveArgs {
+    fn algorithm(&self) -> Algorithm {
+        match self.algorithm {
+            AlgorithmName::Rs => Algorithm::RandomSample,
+            AlgorithmName::Hc => Algorithm::HillClimber {
+                reset_threshold: self.reset_threshold,
+                unary_op: self.operator.clone(),
+            },
+            AlgorithmName::Sa => Algorithm::SimulatedAnnealing {
+                annealing_speed: self.annealing_speed,
+                start_temperature: self.start_temperature,
+                temperature: self.temperature,
+            },
+            AlgorithmName::Ga => Algorithm::Genetic {
+                crossover: self.crossover.clone(),
+                crossover_chance: self.crossover_chance,
+                mu: self.mu,
+                lambda: self.lambda,
+            },
+            AlgorithmName::Ts => Algorithm::TabuSearch {
+                tenure: self.tenure,
+                neighbourhood: self.neighbourhood.unwrap_or(Neighbourhood::N5),
+            },
+            AlgorithmName::Ils => Algorithm::IteratedLocalSearch {
+                neighbourhood: self.neighbourhood.unwrap_or(Neighbourhood::N1),
+                strength: self.strength,
+                acceptance: self.acceptance,
+            },
+            AlgorithmName::Vns => Algorithm::VariableNeighbourhoodSearch {
+                structures: self.neighbourhoods.clone(),
+                patience: self.patience,
+            },
+        }
+    }
+}
+
+#[derive(Args)]
+struct ExperimentArgs {
+    /// Instance files, directories, or names of ones in the instances directory
+    #[arg(required = true)]
+    instances: Vec<String>,
+    /// Algorithm with its parameters, e.g. `ts:tenure=10` or `hc:operator=1swap`; repeatable
+    #[arg(short, long, required = true)]
+    algorithm: Vec<Algorithm>,
+    #[arg(short, long, default_value_t = 10)]
+    repetitions: usize,
+    #[command(flatten)]
+    run: RunArgs,
+    /// Directory for the per-run solutions and the summary
+    #[arg(short, long, default_value = "solutions/experiment")]
+    output: PathBuf,
+}
+
+#[derive(Args)]
+struct ReportArgs {
+    /// Solution files or directories of them, e.g. an experiment output directory
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+    /// Relative gap to the best known makespan (or the lower bound) that counts as a success
+    #[arg(long, default_value_t = 0.0)]
+    target_gap: f64,
+    /// Significance level for the Mann-Whitney U tests
+    #[arg(long, default_value_t = 0.05)]
+    alpha: f64,
+    /// Write the Markdown report here instead of printing it
+    #[arg(long)]
+    markdown: Option<PathBuf>,
+    /// Write the summary table as CSV; the pairwise tests go next to it with a `-comparisons` suffix
+    #[arg(long)]
+    csv: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct EcdfArgs {
+    /// Solution files or directories of them, e.g. an experiment output directory
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+    /// Target makespan, or a gap to the best known makespan (or the lower bound) such as `5%`; repeatable
+    #[arg(short, long, default_values = ["0%", "1%", "5%", "10%"])]
+    target: Vec<Target>,
+    /// Directory for `ecdf.csv`, `ert.csv` and `ecdf.json`
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct GanttArgs {
+    /// Solution JSON written by `solve` or `experiment`
+    solution: PathBuf,
+    /// Where to write the SVG, defaults to the solution path with an `.svg` extension
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Outline the operations on the critical path
+    #[arg(short, long)]
+    critical_path: bool,
+    #[arg(long, default_value_t = 1200)]
+    width: usize,
+    /// Print a text chart sized to the terminal instead of writing an SVG
+    #[arg(long, conflicts_with_all = ["output", "critical_path"])]
+    ascii: bool,
+}
+
+#[derive(Args)]
+struct ConvergenceArgs {
+    /// Solution files or directories of them
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+    /// Where to write the plot; an `.html` extension writes a standalone page, anything else an SVG
+    #[arg(short, long, default_value = "convergence.svg")]
+    output: PathBuf,
+    /// Use a logarithmic time axis
+    #[arg(short, long)]
+    log_time: bool,
+    #[arg(long, default_value_t = 1000)]
+    width: usize,
+    #[arg(long, default_value_t = 600)]
+    height: usize,
+}
+
+fn terminal_width() -> usize {
+    terminal_size::terminal_size().map(|(Width(width), _)| width as usize)
+        .or_else(|| std::env::var("COLUMNS").ok().and_then(|x| x.parse().ok()))
+        .unwrap_or(100)
+}
+
+fn at_least_one(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(0) => Err(String::from("expected at least 1")),
+        Ok(x) => Ok(x),
+        Err(_) => Err(format!("{} is not a whole number", value)),
+    }
+}
+
+fn probability(value: &str) -> Result<f64, String> {
+    match value.parse() {
+        Ok(x) if (0.0..=1.0).contains(&x) => Ok(x),
+        Ok(_) => Err(String::from("expected a probability within [0, 1]")),
+        Err(_) => Err(format!("{} is not a number", value)),
+    }
+}
+
+fn resolve(instance: &str) -> PathBuf {
+    let path = Path::new(instance);
+    if path.exists() { path.to_path_buf() } else { Path::new("instances").join(format!("{}.txt", instance)) }
+}
+
+fn load(instance: &str) -> Vec<Arc<Instance>> {
+    let path = resolve(instance);
+    let instances = if path.is_dir() { Instance::load_dir(&path) } else { Instance::load(&path).map(|x| vec![x]) };
+    let instances = instances.unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    if instances.is_empty() {
+        eprintln!("No instances found in {}", path.display());
+        exit(1);
+    }
+    instances.into_iter().map(Arc::new).collect()
+}
+
+fn solve(args: SolveArgs) {
+    let mut config = args.run.config();
+    if let Some(output) = &args.output { config = config.with_output(output); }
+
+    if resolve(&args.instance).is_dir() {
+        eprintln!("{} is a directory, use `experiment` to solve several instances", args.instance);
+        exit(1);
+    }
+    let instance = load(&args.instance).remove(0);
+    let process = match (args.threaded, args.algorithm()) {
+        (true, Algorithm::RandomSample) => Ok(RandomSample::new(instance, config).solve_threaded()),
+        (true, Algorithm::HillClimber { reset_threshold, unary_op }) =>
+            HillClimber::new(instance, reset_threshold, &unary_op, config).map(|x| x.solve_threaded()).map_err(RunError::from),
+        (_, algorithm) => algorithm.run(instance, config),
+    }.unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+
+    println!("{}", process);
+    if args.gantt { print!("{}", process.gantt_ascii(terminal_width())); }
+    if let Some(output) = args.output {
+        process.save_to_file().unwrap_or_else(|error| {
+            eprintln!("Failed to save the solution to {}: {}", output.display(), error);
+            exit(1);
+        });
+        println!("Saved to {}", output.display());
+    }
+}
+
+fn experiment(args: ExperimentArgs) {
+    let instances = args.instances.iter().flat_map(|x| load(x)).collect();
+    let experiment = Experiment::new(instances, args.algorithm, args.repetitions, args.run.config(), args.output.clone());
+
+    let summaries = experiment.run().unwrap_or_else(|error| {
+        eprintln!("Failed to run the experiment: {}", error);
+        exit(1);
+    });
+    for summary in summaries.iter() {
+        println!("{} {} #{}: {} in {:.2}s", summary.instance, summary.algorithm, summary.repetition,
+                 summary.makespan, summary.seconds);
+    }
+    println!("Saved {} runs to {}", summaries.len(), args.output.display());
+}
+
+fn write(path: &Path, contents: String) {
+    std::fs::write(path, contents).unwrap_or_else(|error| {
+        eprintln!("Failed to write {}: {}", path.display(), error);
+        exit(1);
+    });
+}
+
+fn report(args: ReportArgs) {
+    let results = RunResult::load_all(&args.paths).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    if results.is_empty() {
+        eprintln!("No runs found");
+        exit(1);
+    }
+
+    let report = Report::new(&results, args.target_gap, args.alpha);
+    match &args.markdown {
+        Some(path) => write(path, report.to_markdown()),
+        None => print!("{}", report.to_markdown()),
+    }
+    if let Some(path) = &args.csv {
+        write(path, report.to_csv());
+        let stem = path.file_stem().map(|x| x.to_string_lossy().into_owned()).unwrap_or_default();
+        write(&path.with_file_name(format!("{}-comparisons.csv", stem)), report.comparisons_to_csv());
+    }
+}
+
+fn ecdf(args: EcdfArgs) {
+    let results = RunResult::load_all(&args.paths).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    if results.is_empty() {
+        eprintln!("No runs found");
+        exit(1);
+    }
+
+    let analysis = RuntimeAnalysis::new(&results, &args.target);
+    match &args.output {
+        Some(output) => {
+            std::fs::create_dir_all(output).unwrap_or_else(|error| {
+                eprintln!("Failed to create {}: {}", output.display(), error);
+                exit(1);
+            });
+            write(&output.join("ecdf.csv"), analysis.ecdf_to_csv());
+            write(&output.join("ert.csv"), analysis.ert_to_csv());
+            write(&output.join("ecdf.json"), analysis.to_json().expect("Failed to serialize the analysis"));
+            println!("Saved the ECDF and ERT curves to {}", output.display());
+        }
+        None => print!("{}", analysis.ert_to_csv()),
+    }
+}
+
+fn gantt(args: GanttArgs) {
+    let (instance, schedule) = load_schedule(&args.solution).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+
+    if args.ascii {
+        print!("{}", gantt::ascii(&schedule, terminal_width()));
+        return;
+    }
+
+    let output = args.output.clone().unwrap_or_else(|| args.solution.with_extension("svg"));
+    write(&output, gantt::svg(&instance, &schedule, args.width, args.critical_path));
+    println!("Saved to {}", output.display());
+}
+
+fn plot_convergence(args: ConvergenceArgs) {
+    let results = RunResult::load_all(&args.paths).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        exit(1);
+    });
+    if results.is_empty() {
+        eprintln!("No runs found");
+        exit(1);
+    }
+
+    let plot = match args.output.extension().and_then(|x| x.to_str()) {
+        Some("html") => convergence::html(&results, args.width, args.height, args.log_time),
+        _ => convergence::svg(&results, args.width, args.height, args.log_time),
+    };
+    write(&args.output, plot);
+    println!("Saved {} runs to {}", results.len(), args.output.display());
+}
 
 fn main() {
-    let termination_limit = 10;
-    let is_timed = true;
-    let instance_name = "ta02";
-    let instance_type = InstanceType::TAILLARD;
-
-    let instance = Instance::new(instance_name, instance_type, termination_limit, is_timed);
-    let mut rs = RandomSample::new(instance.clone());
-    let mut hc = HillClimber::new(&instance, 1_676, "nswap");
-    // hc.solve_threaded().save_to_file();
-    let a = (1..=20)
-        .map(|i| block_on(RandomSample::new(instance.clone()).solve_async(i)).termination_counter)
-        .collect_vec();
-
-    let data = serde_json::to_string_pretty(&a).expect("Failed to stringify");
-    let mut fp = File::create(Path::new("experimental/async_performance.json")).expect("Failed to create file");
-    write!(fp, "{}", data).expect("Failed to save data");
-    // rs.solve().save_to_file();
-    // rs.solve_threaded().save_to_file();
+    match Cli::parse().command {
+        Command::Solve(args) => solve(args),
+        Command::Experiment(args) => experiment(args),
+        Command::Report(args) => report(args),
+        Command::Ecdf(args) => ecdf(args),
+        Command::Gantt(args) => gantt(args),
+        Command::Convergence(args) => plot_convergence(args),
+    }
 }